@@ -0,0 +1,670 @@
+#![allow(dead_code)]
+
+use ruint::aliases::U256;
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamType {
+    Uint(usize),
+    Int(usize),
+    Address,
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Uint(U256),
+    Int(U256),
+    Address([u8; 20]),
+    Bool(bool),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<Token>),
+    FixedArray(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<ParamType>,
+    pub outputs: Vec<ParamType>,
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+pub fn parse_address(value: &str) -> Result<[u8; 20], String> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| format!("invalid address {}: {}", value, e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("invalid address {}: expected 20 bytes", value))
+}
+
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+pub fn address_word(address: &str) -> Result<[u8; 32], String> {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(&parse_address(address)?);
+    Ok(word)
+}
+
+pub fn decode_string(data: &[u8]) -> Result<String, String> {
+    decode(&[ParamType::String], data)?
+        .pop()
+        .and_then(Token::into_string)
+        .ok_or_else(|| "expected an ABI-encoded string".to_string())
+}
+
+pub fn checksum_address(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+    let mut result = String::with_capacity(42);
+    result.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+        if c.is_ascii_alphabetic() && nibble >= 8 {
+            result.push(c.to_ascii_uppercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+pub fn parse_uint(value: &str) -> Result<U256, String> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16),
+        None => U256::from_str_radix(value, 10),
+    };
+    parsed.map_err(|e| format!("invalid integer {}: {}", value, e))
+}
+
+fn parse_hex_bytes(value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value.trim_start_matches("0x")).map_err(|e| format!("invalid hex {}: {}", value, e))
+}
+
+impl ParamType {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (param, rest) = parse_type(value.trim())?;
+        if !rest.trim().is_empty() {
+            return Err(format!("unexpected input after type: {}", rest));
+        }
+        Ok(param)
+    }
+
+    pub fn from_json(param: &Value) -> Result<Self, String> {
+        let kind = param["type"]
+            .as_str()
+            .ok_or("ABI parameter is missing a type")?;
+        match kind.strip_prefix("tuple") {
+            Some(suffix) => {
+                let components = param["components"]
+                    .as_array()
+                    .ok_or("tuple parameter is missing components")?
+                    .iter()
+                    .map(ParamType::from_json)
+                    .collect::<Result<Vec<_>, _>>()?;
+                parse_array_suffix(ParamType::Tuple(components), suffix).and_then(
+                    |(param, rest)| match rest.is_empty() {
+                        true => Ok(param),
+                        false => Err(format!("invalid tuple type {}", kind)),
+                    },
+                )
+            }
+            None => ParamType::parse(kind),
+        }
+    }
+
+    pub fn canonical(&self) -> String {
+        match self {
+            ParamType::Uint(bits) => format!("uint{}", bits),
+            ParamType::Int(bits) => format!("int{}", bits),
+            ParamType::Address => "address".to_string(),
+            ParamType::Bool => "bool".to_string(),
+            ParamType::FixedBytes(size) => format!("bytes{}", size),
+            ParamType::Bytes => "bytes".to_string(),
+            ParamType::String => "string".to_string(),
+            ParamType::Array(inner) => format!("{}[]", inner.canonical()),
+            ParamType::FixedArray(inner, size) => format!("{}[{}]", inner.canonical(), size),
+            ParamType::Tuple(params) => format!("({})", canonical_list(params)),
+        }
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(inner, _) => inner.is_dynamic(),
+            ParamType::Tuple(params) => params.iter().any(ParamType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    fn head_size(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            ParamType::FixedArray(inner, size) => inner.head_size() * size,
+            ParamType::Tuple(params) => params.iter().map(ParamType::head_size).sum(),
+            _ => 32,
+        }
+    }
+}
+
+fn canonical_list(params: &[ParamType]) -> String {
+    params
+        .iter()
+        .map(ParamType::canonical)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_type(value: &str) -> Result<(ParamType, &str), String> {
+    let value = value.trim_start();
+    let value = value.strip_prefix("tuple").unwrap_or(value);
+
+    if value.starts_with('(') {
+        let (params, rest) = parse_param_list(value)?;
+        return parse_array_suffix(ParamType::Tuple(params), rest);
+    }
+
+    let end = value
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(value.len());
+    let (name, rest) = value.split_at(end);
+    let base = match name {
+        "address" => ParamType::Address,
+        "bool" => ParamType::Bool,
+        "string" => ParamType::String,
+        "bytes" => ParamType::Bytes,
+        "uint" => ParamType::Uint(256),
+        "int" => ParamType::Int(256),
+        _ => {
+            let sized = |prefix: &str| {
+                name.strip_prefix(prefix)
+                    .and_then(|size| size.parse::<usize>().ok())
+            };
+            if let Some(bits) = sized("uint").filter(|b| *b > 0 && *b <= 256 && b % 8 == 0) {
+                ParamType::Uint(bits)
+            } else if let Some(bits) = sized("int").filter(|b| *b > 0 && *b <= 256 && b % 8 == 0) {
+                ParamType::Int(bits)
+            } else if let Some(size) = sized("bytes").filter(|s| *s > 0 && *s <= 32) {
+                ParamType::FixedBytes(size)
+            } else {
+                return Err(format!("unsupported ABI type: {}", name));
+            }
+        }
+    };
+    parse_array_suffix(base, rest)
+}
+
+fn parse_array_suffix(mut param: ParamType, mut rest: &str) -> Result<(ParamType, &str), String> {
+    while let Some(inner) = rest.strip_prefix('[') {
+        let end = inner.find(']').ok_or("unterminated array type")?;
+        let size = &inner[..end];
+        param = if size.is_empty() {
+            ParamType::Array(Box::new(param))
+        } else {
+            let size = size
+                .parse()
+                .map_err(|_| format!("invalid array size: {}", size))?;
+            ParamType::FixedArray(Box::new(param), size)
+        };
+        rest = &inner[end + 1..];
+    }
+    Ok((param, rest))
+}
+
+fn parse_param_list(value: &str) -> Result<(Vec<ParamType>, &str), String> {
+    let mut rest = value
+        .trim_start()
+        .strip_prefix('(')
+        .ok_or("expected parameter list")?
+        .trim_start();
+    let mut params = Vec::new();
+
+    if let Some(after) = rest.strip_prefix(')') {
+        return Ok((params, after));
+    }
+
+    loop {
+        let (param, after) = parse_type(rest)?;
+        params.push(param);
+
+        let end = after
+            .find([',', ')'])
+            .ok_or("unterminated parameter list")?;
+        let separator = after.as_bytes()[end];
+        rest = after[end + 1..].trim_start();
+        if separator == b')' {
+            return Ok((params, rest));
+        }
+    }
+}
+
+impl Function {
+    pub fn parse(signature: &str) -> Result<Self, String> {
+        let signature = signature.trim();
+        let signature = signature.strip_prefix("function ").unwrap_or(signature);
+        let open = signature
+            .find('(')
+            .ok_or("function signature is missing '('")?;
+        let name = signature[..open].trim().to_string();
+        let (inputs, mut rest) = parse_param_list(&signature[open..])?;
+
+        let mut outputs = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            if rest.starts_with('(') {
+                let (params, after) = parse_param_list(rest)?;
+                outputs = params;
+                rest = after;
+                continue;
+            }
+            let end = rest.find([' ', '(']).unwrap_or(rest.len());
+            match &rest[..end] {
+                "view" | "pure" | "external" | "public" | "payable" | "nonpayable" | "returns" => {
+                    rest = &rest[end..]
+                }
+                other => return Err(format!("unexpected token in signature: {}", other)),
+            }
+        }
+
+        Ok(Self {
+            name,
+            inputs,
+            outputs,
+        })
+    }
+
+    pub fn from_json_abi(abi: &Value, name: &str) -> Result<Self, String> {
+        let entries = abi
+            .as_array()
+            .or_else(|| abi["abi"].as_array())
+            .ok_or("ABI must be a JSON array")?;
+
+        let mut matches = Vec::new();
+        for entry in entries {
+            let kind = entry["type"].as_str().unwrap_or("function");
+            if kind != "function" && kind != "constructor" {
+                continue;
+            }
+            let params = |key: &str| -> Result<Vec<ParamType>, String> {
+                entry[key]
+                    .as_array()
+                    .map(|params| params.iter().map(ParamType::from_json).collect())
+                    .unwrap_or_else(|| Ok(Vec::new()))
+            };
+            let function = Function {
+                name: entry["name"].as_str().unwrap_or(kind).to_string(),
+                inputs: params("inputs")?,
+                outputs: params("outputs")?,
+            };
+            if function.name == name || function.signature() == name {
+                matches.push(function);
+            }
+        }
+
+        match matches.len() {
+            0 => Err(format!("function {} not found in ABI", name)),
+            1 => Ok(matches.remove(0)),
+            _ => Err(format!(
+                "function {} is overloaded, use one of: {}",
+                name,
+                matches
+                    .iter()
+                    .map(Function::signature)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, canonical_list(&self.inputs))
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    pub fn encode_input(&self, tokens: &[Token]) -> Result<Vec<u8>, String> {
+        if tokens.len() != self.inputs.len() {
+            return Err(format!(
+                "{} expects {} arguments, got {}",
+                self.signature(),
+                self.inputs.len(),
+                tokens.len()
+            ));
+        }
+        let mut data = self.selector().to_vec();
+        data.extend(encode(tokens));
+        Ok(data)
+    }
+
+    pub fn parse_args(&self, args: &[String]) -> Result<Vec<Token>, String> {
+        if args.len() != self.inputs.len() {
+            return Err(format!(
+                "{} expects {} arguments, got {}",
+                self.signature(),
+                self.inputs.len(),
+                args.len()
+            ));
+        }
+        self.inputs
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| Token::parse(param, arg))
+            .collect()
+    }
+
+    pub fn encode_args(&self, args: &[String]) -> Result<Vec<u8>, String> {
+        self.encode_input(&self.parse_args(args)?)
+    }
+
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<Token>, String> {
+        decode(&self.outputs, data)
+    }
+}
+
+impl Token {
+    pub fn parse(param: &ParamType, value: &str) -> Result<Self, String> {
+        let json = match param {
+            ParamType::Array(_) | ParamType::FixedArray(_, _) | ParamType::Tuple(_) => {
+                serde_json::from_str(value).map_err(|e| format!("invalid {}: {}", value, e))?
+            }
+            _ => Value::String(value.to_string()),
+        };
+        Token::from_json(param, &json)
+    }
+
+    pub fn from_json(param: &ParamType, value: &Value) -> Result<Self, String> {
+        let text = || {
+            value
+                .as_str()
+                .map(str::to_string)
+                .or_else(|| value.as_u64().map(|v| v.to_string()))
+                .or_else(|| value.as_bool().map(|v| v.to_string()))
+                .ok_or_else(|| format!("expected {} but got {}", param.canonical(), value))
+        };
+        let items = |expected: Option<usize>| -> Result<&Vec<Value>, String> {
+            let items = value
+                .as_array()
+                .ok_or_else(|| format!("expected array for {}", param.canonical()))?;
+            match expected {
+                Some(len) if items.len() != len => Err(format!(
+                    "{} expects {} elements, got {}",
+                    param.canonical(),
+                    len,
+                    items.len()
+                )),
+                _ => Ok(items),
+            }
+        };
+
+        match param {
+            ParamType::Uint(bits) => {
+                let parsed = parse_uint(&text()?)?;
+                if parsed.bit_len() > *bits {
+                    return Err(format!("{} does not fit in uint{}", parsed, bits));
+                }
+                Ok(Token::Uint(parsed))
+            }
+            ParamType::Int(bits) => {
+                let text = text()?;
+                let (negative, digits) = match text.strip_prefix('-') {
+                    Some(digits) => (true, digits),
+                    None => (false, text.as_str()),
+                };
+                let magnitude = parse_uint(digits)?;
+                let limit = U256::from(1u8) << (bits - 1);
+                if magnitude > limit || (magnitude == limit && !negative) {
+                    return Err(format!("{} does not fit in int{}", text, bits));
+                }
+                Ok(Token::Int(if negative {
+                    magnitude.wrapping_neg()
+                } else {
+                    magnitude
+                }))
+            }
+            ParamType::Address => Ok(Token::Address(parse_address(&text()?)?)),
+            ParamType::Bool => match text()?.as_str() {
+                "true" | "1" => Ok(Token::Bool(true)),
+                "false" | "0" => Ok(Token::Bool(false)),
+                other => Err(format!("invalid bool: {}", other)),
+            },
+            ParamType::FixedBytes(size) => {
+                let bytes = parse_hex_bytes(&text()?)?;
+                if bytes.len() != *size {
+                    return Err(format!("bytes{} expects {} bytes", size, size));
+                }
+                Ok(Token::FixedBytes(bytes))
+            }
+            ParamType::Bytes => Ok(Token::Bytes(parse_hex_bytes(&text()?)?)),
+            ParamType::String => Ok(Token::String(text()?)),
+            ParamType::Array(inner) => items(None)?
+                .iter()
+                .map(|item| Token::from_json(inner, item))
+                .collect::<Result<_, _>>()
+                .map(Token::Array),
+            ParamType::FixedArray(inner, size) => items(Some(*size))?
+                .iter()
+                .map(|item| Token::from_json(inner, item))
+                .collect::<Result<_, _>>()
+                .map(Token::FixedArray),
+            ParamType::Tuple(params) => items(Some(params.len()))?
+                .iter()
+                .zip(params)
+                .map(|(item, param)| Token::from_json(param, item))
+                .collect::<Result<_, _>>()
+                .map(Token::Tuple),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            Token::Uint(value) => Value::String(value.to_string()),
+            Token::Int(value) if value.bit(255) => {
+                Value::String(format!("-{}", value.wrapping_neg()))
+            }
+            Token::Int(value) => Value::String(value.to_string()),
+            Token::Address(address) => Value::String(checksum_address(address)),
+            Token::Bool(value) => Value::Bool(*value),
+            Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+                Value::String(format!("0x{}", hex::encode(bytes)))
+            }
+            Token::String(value) => Value::String(value.clone()),
+            Token::Array(items) | Token::FixedArray(items) | Token::Tuple(items) => {
+                Value::Array(items.iter().map(Token::to_json).collect())
+            }
+        }
+    }
+
+    pub fn into_uint(self) -> Option<U256> {
+        match self {
+            Token::Uint(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn into_address(self) -> Option<[u8; 20]> {
+        match self {
+            Token::Address(address) => Some(address),
+            _ => None,
+        }
+    }
+
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            Token::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn into_bool(self) -> Option<bool> {
+        match self {
+            Token::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            Token::Bytes(bytes) | Token::FixedBytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn into_array(self) -> Option<Vec<Token>> {
+        match self {
+            Token::Array(items) | Token::FixedArray(items) | Token::Tuple(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+            Token::FixedArray(items) | Token::Tuple(items) => items.iter().any(Token::is_dynamic),
+            _ => false,
+        }
+    }
+}
+
+fn word(value: U256) -> [u8; 32] {
+    value.to_be_bytes::<32>()
+}
+
+fn padded(bytes: &[u8]) -> Vec<u8> {
+    let mut data = bytes.to_vec();
+    data.resize(bytes.len().div_ceil(32) * 32, 0);
+    data
+}
+
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let encoded: Vec<Vec<u8>> = tokens.iter().map(encode_token).collect();
+    let head_len: usize = tokens
+        .iter()
+        .zip(&encoded)
+        .map(|(token, data)| if token.is_dynamic() { 32 } else { data.len() })
+        .sum();
+
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+    for (token, data) in tokens.iter().zip(encoded) {
+        if token.is_dynamic() {
+            head.extend(word(U256::from(head_len + tail.len())));
+            tail.extend(data);
+        } else {
+            head.extend(data);
+        }
+    }
+    head.extend(tail);
+    head
+}
+
+fn encode_token(token: &Token) -> Vec<u8> {
+    match token {
+        Token::Uint(value) | Token::Int(value) => word(*value).to_vec(),
+        Token::Address(address) => {
+            let mut data = vec![0u8; 12];
+            data.extend_from_slice(address);
+            data
+        }
+        Token::Bool(value) => word(U256::from(*value as u8)).to_vec(),
+        Token::FixedBytes(bytes) => padded(bytes),
+        Token::Bytes(bytes) => {
+            let mut data = word(U256::from(bytes.len())).to_vec();
+            data.extend(padded(bytes));
+            data
+        }
+        Token::String(value) => encode_token(&Token::Bytes(value.as_bytes().to_vec())),
+        Token::Array(items) => {
+            let mut data = word(U256::from(items.len())).to_vec();
+            data.extend(encode(items));
+            data
+        }
+        Token::FixedArray(items) | Token::Tuple(items) => encode(items),
+    }
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<[u8; 32], String> {
+    data.get(offset..offset + 32)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or_else(|| format!("data too short: need 32 bytes at offset {}", offset))
+}
+
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, String> {
+    let value = U256::from_be_bytes(read_word(data, offset)?);
+    if value > U256::from(data.len()) {
+        return Err(format!("offset or length {} out of bounds", value));
+    }
+    Ok(value.to::<usize>())
+}
+
+pub fn decode(params: &[ParamType], data: &[u8]) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::with_capacity(params.len());
+    let mut head = 0;
+    for param in params {
+        let token = if param.is_dynamic() {
+            let offset = read_usize(data, head)?;
+            decode_token(param, &data[offset..])?
+        } else {
+            decode_token(param, data.get(head..).ok_or("data too short")?)?
+        };
+        tokens.push(token);
+        head += param.head_size();
+    }
+    Ok(tokens)
+}
+
+fn decode_token(param: &ParamType, data: &[u8]) -> Result<Token, String> {
+    match param {
+        ParamType::Uint(_) => Ok(Token::Uint(U256::from_be_bytes(read_word(data, 0)?))),
+        ParamType::Int(_) => Ok(Token::Int(U256::from_be_bytes(read_word(data, 0)?))),
+        ParamType::Address => {
+            let word = read_word(data, 0)?;
+            Ok(Token::Address(word[12..].try_into().unwrap()))
+        }
+        ParamType::Bool => Ok(Token::Bool(read_word(data, 0)?[31] != 0)),
+        ParamType::FixedBytes(size) => Ok(Token::FixedBytes(read_word(data, 0)?[..*size].to_vec())),
+        ParamType::Bytes | ParamType::String => {
+            let len = read_usize(data, 0)?;
+            let bytes = data
+                .get(32..32 + len)
+                .ok_or("dynamic value exceeds data length")?
+                .to_vec();
+            match param {
+                ParamType::String => String::from_utf8(bytes)
+                    .map(Token::String)
+                    .map_err(|e| e.to_string()),
+                _ => Ok(Token::Bytes(bytes)),
+            }
+        }
+        ParamType::Array(inner) => {
+            let len = read_usize(data, 0)?;
+            let params = vec![(**inner).clone(); len];
+            decode(&params, &data[32..]).map(Token::Array)
+        }
+        ParamType::FixedArray(inner, size) => {
+            let params = vec![(**inner).clone(); *size];
+            decode(&params, data).map(Token::FixedArray)
+        }
+        ParamType::Tuple(params) => decode(params, data).map(Token::Tuple),
+    }
+}
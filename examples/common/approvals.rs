@@ -0,0 +1,235 @@
+#![allow(dead_code)]
+
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::policies::types::{
+        CreateApprovalDecisionRequest, CreateApprovalDecisionRequestBody, ListApprovalsQueryStatus,
+        ListApprovalsRequest, ListApprovalsRequestQuery, ListApprovalsResponseItem,
+        Value as DecisionValue,
+    },
+};
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const REQUEST_KEYS: &[&str] = &[
+    "transferRequest",
+    "transactionRequest",
+    "signatureRequest",
+    "changeRequest",
+    "blockchainEvent",
+];
+
+#[derive(Clone, Debug)]
+pub struct Decision {
+    pub user_id: String,
+    pub value: String,
+    pub date: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TriggeredPolicy {
+    pub policy_id: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Approval {
+    pub id: String,
+    pub status: String,
+    pub initiator_id: String,
+    pub date_created: Option<String>,
+    pub expiration_date: Option<String>,
+    pub activity_kind: Option<String>,
+    pub request_kind: Option<String>,
+    pub wallet_id: Option<String>,
+    pub network: Option<String>,
+    pub to: Option<String>,
+    pub amount: Option<String>,
+    pub asset: Option<String>,
+    pub triggered_policies: Vec<TriggeredPolicy>,
+    pub decisions: Vec<Decision>,
+}
+
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+pub fn parse_timestamp(value: &str) -> Option<u64> {
+    let (date, time) = value.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+
+    let time = time.trim_end_matches('Z');
+    let time = time.split(['+', '.']).next()?;
+    let mut time = time.splitn(3, ':').map(str::parse::<i64>);
+    let (hour, minute, second) = (time.next()?.ok()?, time.next()?.ok()?, time.next()?.ok()?);
+
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    u64::try_from(days * 86_400 + hour * 3600 + minute * 60 + second).ok()
+}
+
+impl Approval {
+    pub fn from_json(raw: Value) -> Result<Self, String> {
+        serde_json::from_value(raw)
+            .map(Self::from_item)
+            .map_err(|e| format!("invalid approval: {}", e))
+    }
+
+    pub fn from_item(item: ListApprovalsResponseItem) -> Self {
+        let activity = serde_json::to_value(&item.activity).unwrap_or_default();
+        let (request_kind, request) = REQUEST_KEYS
+            .iter()
+            .find(|key| activity[**key].is_object())
+            .map(|key| (Some(key.to_string()), &activity[*key]))
+            .unwrap_or((None, &Value::Null));
+        let body = match &request["requestBody"] {
+            Value::Object(_) => &request["requestBody"],
+            _ => request,
+        };
+
+        let triggered_policies = item
+            .policy_evaluations
+            .into_iter()
+            .filter(|evaluation| evaluation.triggered)
+            .map(|evaluation| TriggeredPolicy {
+                policy_id: evaluation.policy_id,
+                reason: Some(evaluation.reason).filter(|reason| !reason.is_empty()),
+            })
+            .collect();
+
+        let decisions = item
+            .decisions
+            .into_iter()
+            .map(|decision| Decision {
+                user_id: decision.user_id,
+                value: format!("{:?}", decision.value),
+                date: decision.date,
+                reason: decision.reason,
+            })
+            .collect();
+
+        Approval {
+            id: item.id,
+            status: format!("{:?}", item.status),
+            initiator_id: item.initiator_id,
+            date_created: item.date_created,
+            expiration_date: item.expiration_date,
+            activity_kind: text(&activity["kind"]),
+            request_kind,
+            wallet_id: text(&request["walletId"]),
+            network: text(&request["network"]),
+            to: text(&body["to"]),
+            amount: text(&body["amount"]).or_else(|| text(&body["value"])),
+            asset: text(&body["contract"])
+                .or_else(|| text(&body["mint"]))
+                .or_else(|| text(&body["assetId"]))
+                .or_else(|| text(&body["kind"])),
+            triggered_policies,
+            decisions,
+        }
+    }
+
+    pub fn created_at(&self) -> Option<u64> {
+        self.date_created.as_deref().and_then(parse_timestamp)
+    }
+
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expiration_date.as_deref().and_then(parse_timestamp)
+    }
+
+    pub fn decided_by(&self, user_id: &str) -> bool {
+        self.decisions
+            .iter()
+            .any(|decision| decision.user_id == user_id)
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {} {} to {} from wallet {}",
+            self.activity_kind.as_deref().unwrap_or("activity"),
+            self.amount.as_deref().unwrap_or("?"),
+            self.asset.as_deref().unwrap_or(""),
+            self.to.as_deref().unwrap_or("?"),
+            self.wallet_id.as_deref().unwrap_or("?")
+        )
+    }
+}
+
+pub async fn list_pending(
+    client: &DfnsApiClient,
+    approver_id: Option<&str>,
+) -> Result<Vec<Approval>, String> {
+    let mut approvals = Vec::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListApprovalsRequest {
+            query: Some(ListApprovalsRequestQuery {
+                limit: Some("100".to_string()),
+                status: Some(ListApprovalsQueryStatus::Pending),
+                approver_id: approver_id.map(str::to_string),
+                initiator_id: None,
+                pagination_token: pagination_token.take(),
+            }),
+        };
+
+        let response = client
+            .policies()
+            .list_approvals(Some(request))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        approvals.extend(response.items.into_iter().map(Approval::from_item));
+
+        match response.next_page_token {
+            Some(token) => pagination_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(approvals)
+}
+
+pub async fn decide(
+    client: &DfnsApiClient,
+    approval_id: &str,
+    approve: bool,
+    reason: &str,
+) -> Result<String, String> {
+    let request = CreateApprovalDecisionRequest {
+        approval_id: approval_id.to_string(),
+        body: CreateApprovalDecisionRequestBody {
+            reason: Some(reason.to_string()),
+            value: if approve {
+                DecisionValue::Approved
+            } else {
+                DecisionValue::Denied
+            },
+        },
+    };
+
+    let response = client
+        .policies()
+        .create_approval_decision(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(format!("{:?}", response.status))
+}
@@ -0,0 +1,258 @@
+#![allow(dead_code)]
+
+pub const MSG_SEND: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const MSG_DELEGATE: &str = "/cosmos.staking.v1beta1.MsgDelegate";
+pub const MSG_UNDELEGATE: &str = "/cosmos.staking.v1beta1.MsgUndelegate";
+pub const MSG_TRANSFER: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+pub const SECP256K1_PUBKEY: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+const SIGN_MODE_DIRECT: u64 = 1;
+const WIRE_VARINT: u64 = 0;
+const WIRE_LEN: u64 = 2;
+
+#[derive(Default)]
+struct ProtoWriter {
+    buf: Vec<u8>,
+}
+
+impl ProtoWriter {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn key(&mut self, field: u64, wire_type: u64) {
+        self.varint((field << 3) | wire_type);
+    }
+
+    fn uint64(&mut self, field: u64, value: u64) -> &mut Self {
+        if value != 0 {
+            self.key(field, WIRE_VARINT);
+            self.varint(value);
+        }
+        self
+    }
+
+    fn bytes(&mut self, field: u64, value: &[u8]) -> &mut Self {
+        if !value.is_empty() {
+            self.key(field, WIRE_LEN);
+            self.varint(value.len() as u64);
+            self.buf.extend_from_slice(value);
+        }
+        self
+    }
+
+    fn string(&mut self, field: u64, value: &str) -> &mut Self {
+        self.bytes(field, value.as_bytes())
+    }
+
+    fn message(&mut self, field: u64, value: &[u8]) -> &mut Self {
+        self.key(field, WIRE_LEN);
+        self.varint(value.len() as u64);
+        self.buf.extend_from_slice(value);
+        self
+    }
+
+    fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+#[derive(Clone)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: String,
+}
+
+impl Coin {
+    pub fn new(amount: impl ToString, denom: &str) -> Self {
+        Self {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        ProtoWriter::default()
+            .string(1, &self.denom)
+            .string(2, &self.amount)
+            .finish()
+    }
+}
+
+pub struct Any {
+    pub type_url: String,
+    pub value: Vec<u8>,
+}
+
+impl Any {
+    pub fn new(type_url: &str, value: Vec<u8>) -> Self {
+        Self {
+            type_url: type_url.to_string(),
+            value,
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        ProtoWriter::default()
+            .string(1, &self.type_url)
+            .bytes(2, &self.value)
+            .finish()
+    }
+}
+
+pub fn msg_send(from: &str, to: &str, amount: &[Coin]) -> Any {
+    let mut writer = ProtoWriter::default();
+    writer.string(1, from).string(2, to);
+    for coin in amount {
+        writer.message(3, &coin.encode());
+    }
+    Any::new(MSG_SEND, writer.finish())
+}
+
+fn staking_msg(type_url: &str, delegator: &str, validator: &str, amount: &Coin) -> Any {
+    let value = ProtoWriter::default()
+        .string(1, delegator)
+        .string(2, validator)
+        .message(3, &amount.encode())
+        .finish();
+    Any::new(type_url, value)
+}
+
+pub fn msg_delegate(delegator: &str, validator: &str, amount: &Coin) -> Any {
+    staking_msg(MSG_DELEGATE, delegator, validator, amount)
+}
+
+pub fn msg_undelegate(delegator: &str, validator: &str, amount: &Coin) -> Any {
+    staking_msg(MSG_UNDELEGATE, delegator, validator, amount)
+}
+
+pub struct IbcTransfer<'a> {
+    pub source_port: &'a str,
+    pub source_channel: &'a str,
+    pub token: Coin,
+    pub sender: &'a str,
+    pub receiver: &'a str,
+    pub timeout_revision_number: u64,
+    pub timeout_revision_height: u64,
+    pub timeout_timestamp_nanos: u64,
+    pub memo: &'a str,
+}
+
+pub fn msg_ibc_transfer(transfer: &IbcTransfer<'_>) -> Any {
+    let timeout_height = ProtoWriter::default()
+        .uint64(1, transfer.timeout_revision_number)
+        .uint64(2, transfer.timeout_revision_height)
+        .finish();
+
+    let value = ProtoWriter::default()
+        .string(1, transfer.source_port)
+        .string(2, transfer.source_channel)
+        .message(3, &transfer.token.encode())
+        .string(4, transfer.sender)
+        .string(5, transfer.receiver)
+        .message(6, &timeout_height)
+        .uint64(7, transfer.timeout_timestamp_nanos)
+        .string(8, transfer.memo)
+        .finish();
+    Any::new(MSG_TRANSFER, value)
+}
+
+pub struct Fee {
+    pub amount: Vec<Coin>,
+    pub gas_limit: u64,
+}
+
+pub struct SignDoc {
+    pub body_bytes: Vec<u8>,
+    pub auth_info_bytes: Vec<u8>,
+    pub chain_id: String,
+    pub account_number: u64,
+}
+
+pub struct SignDocParams<'a> {
+    pub messages: &'a [Any],
+    pub memo: &'a str,
+    pub public_key: &'a [u8],
+    pub sequence: u64,
+    pub fee: &'a Fee,
+    pub chain_id: &'a str,
+    pub account_number: u64,
+}
+
+impl SignDoc {
+    pub fn new(params: &SignDocParams<'_>) -> Self {
+        let mut body = ProtoWriter::default();
+        for message in params.messages {
+            body.message(1, &message.encode());
+        }
+        body.string(2, params.memo);
+
+        let public_key = Any::new(
+            SECP256K1_PUBKEY,
+            ProtoWriter::default().bytes(1, params.public_key).finish(),
+        );
+        let single = ProtoWriter::default().uint64(1, SIGN_MODE_DIRECT).finish();
+        let mode_info = ProtoWriter::default().message(1, &single).finish();
+        let signer_info = ProtoWriter::default()
+            .message(1, &public_key.encode())
+            .message(2, &mode_info)
+            .uint64(3, params.sequence)
+            .finish();
+
+        let mut fee = ProtoWriter::default();
+        for coin in &params.fee.amount {
+            fee.message(1, &coin.encode());
+        }
+        fee.uint64(2, params.fee.gas_limit);
+
+        let auth_info = ProtoWriter::default()
+            .message(1, &signer_info)
+            .message(2, &fee.finish())
+            .finish();
+
+        Self {
+            body_bytes: body.finish(),
+            auth_info_bytes: auth_info,
+            chain_id: params.chain_id.to_string(),
+            account_number: params.account_number,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        ProtoWriter::default()
+            .bytes(1, &self.body_bytes)
+            .bytes(2, &self.auth_info_bytes)
+            .string(3, &self.chain_id)
+            .uint64(4, self.account_number)
+            .finish()
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.encode()))
+    }
+
+    pub fn into_tx_raw(self, signature: &[u8]) -> Vec<u8> {
+        ProtoWriter::default()
+            .bytes(1, &self.body_bytes)
+            .bytes(2, &self.auth_info_bytes)
+            .message(3, signature)
+            .finish()
+    }
+}
+
+pub fn signature_from_rs(r: &str, s: &str) -> Result<Vec<u8>, String> {
+    let mut signature = Vec::with_capacity(64);
+    for part in [r, s] {
+        let bytes = hex::decode(part.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+        if bytes.len() > 32 {
+            return Err("signature component is longer than 32 bytes".to_string());
+        }
+        signature.extend(std::iter::repeat_n(0u8, 32 - bytes.len()));
+        signature.extend_from_slice(&bytes);
+    }
+    Ok(signature)
+}
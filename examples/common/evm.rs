@@ -0,0 +1,326 @@
+#![allow(dead_code)]
+
+use crate::abi::{self, Function, Token};
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        networks::types::{
+            Body, GetFeesQueryNetwork, GetFeesRequest, Query, ReadContractBodyKind,
+            ReadContractBodyNetwork, ReadContractRequest,
+        },
+        wallets::types::{
+            BroadcastTransactionBody, BroadcastTransactionBodyKind, BroadcastTransactionRequest,
+            GetTransactionRequest, Nonce,
+        },
+    },
+};
+use ruint::aliases::U256;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+pub const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const TX_GAS: u64 = 21_000;
+const TX_CREATE_GAS: u64 = 32_000;
+const TX_DATA_ZERO_GAS: u64 = 4;
+const TX_DATA_NON_ZERO_GAS: u64 = 16;
+const INIT_CODE_WORD_GAS: u64 = 2;
+const TERMINAL_STATUSES: &[&str] = &["Confirmed", "Failed", "Rejected"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeeSpeed {
+    Slow,
+    Standard,
+    Fast,
+}
+
+impl FeeSpeed {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "slow" => Ok(FeeSpeed::Slow),
+            "standard" => Ok(FeeSpeed::Standard),
+            "fast" => Ok(FeeSpeed::Fast),
+            other => Err(format!(
+                "unknown fee speed {} (expected slow, standard or fast)",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Eip1559Fees {
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EvmTransaction {
+    pub to: Option<String>,
+    pub data: Vec<u8>,
+    pub value: U256,
+    pub gas_limit: Option<u64>,
+    pub nonce: Option<u64>,
+    pub fees: Option<Eip1559Fees>,
+    pub external_id: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TransactionStatus {
+    pub status: String,
+    pub tx_hash: Option<String>,
+    pub details: Value,
+}
+
+impl TransactionStatus {
+    pub fn is_terminal(&self) -> bool {
+        TERMINAL_STATUSES.contains(&self.status.as_str())
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.status == "Confirmed"
+    }
+}
+
+pub fn convert_network<T: DeserializeOwned>(network: &impl Serialize) -> Result<T, String> {
+    serde_json::to_value(network)
+        .and_then(serde_json::from_value)
+        .map_err(|e| format!("unsupported network: {}", e))
+}
+
+pub fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+pub async fn call_raw(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    contract: &str,
+    data: &[u8],
+) -> Result<Vec<u8>, String> {
+    let request = ReadContractRequest {
+        body: Body {
+            contract: contract.to_string(),
+            data: format!("0x{}", hex::encode(data)),
+            kind: ReadContractBodyKind::Evm,
+            network: network.clone(),
+        },
+    };
+
+    let response = client
+        .networks()
+        .read_contract(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    hex::decode(response.data.trim_start_matches("0x")).map_err(|e| e.to_string())
+}
+
+pub async fn call(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    contract: &str,
+    function: &Function,
+    args: &[Token],
+) -> Result<Vec<Token>, String> {
+    let data = call_raw(client, network, contract, &function.encode_input(args)?).await?;
+    function.decode_output(&data)
+}
+
+pub fn format_units(value: U256, decimals: u8) -> String {
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+pub fn parse_units(value: &str, decimals: u8) -> Result<U256, String> {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if fraction.len() > decimals as usize {
+        return Err(format!(
+            "{} has more than {} decimal places",
+            value, decimals
+        ));
+    }
+
+    let digits = format!(
+        "{}{:0<width$}",
+        integer,
+        fraction,
+        width = decimals as usize
+    );
+    U256::from_str_radix(if digits.is_empty() { "0" } else { &digits }, 10)
+        .map_err(|e| format!("invalid amount {}: {}", value, e))
+}
+
+pub async fn suggest_fees(
+    client: &DfnsApiClient,
+    network: GetFeesQueryNetwork,
+    speed: FeeSpeed,
+) -> Result<Eip1559Fees, String> {
+    let response = client
+        .networks()
+        .get_fees(Some(GetFeesRequest {
+            query: Some(Query { network }),
+        }))
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let tier = match speed {
+        FeeSpeed::Slow => (
+            response.slow.max_fee_per_gas,
+            response.slow.max_priority_fee_per_gas,
+        ),
+        FeeSpeed::Standard => (
+            response.standard.max_fee_per_gas,
+            response.standard.max_priority_fee_per_gas,
+        ),
+        FeeSpeed::Fast => (
+            response.fast.max_fee_per_gas,
+            response.fast.max_priority_fee_per_gas,
+        ),
+    };
+
+    match tier {
+        (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => Ok(Eip1559Fees {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }),
+        _ => Err(format!(
+            "network does not report EIP-1559 fees for {:?}",
+            speed
+        )),
+    }
+}
+
+pub async fn broadcast(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    transaction: &EvmTransaction,
+) -> Result<String, String> {
+    let request = BroadcastTransactionRequest {
+        wallet_id: wallet_id.to_string(),
+        body: BroadcastTransactionBody {
+            kind: BroadcastTransactionBodyKind::Eip1559,
+            transaction: None,
+            data: Some(format!("0x{}", hex::encode(&transaction.data))),
+            gas_limit: transaction.gas_limit.map(|gas| gas.to_string()),
+            nonce: transaction.nonce.map(|nonce| Nonce::Double(nonce as f64)),
+            to: transaction.to.clone(),
+            value: Some(transaction.value.to_string()),
+            max_fee_per_gas: transaction
+                .fees
+                .as_ref()
+                .map(|fees| fees.max_fee_per_gas.clone()),
+            max_priority_fee_per_gas: transaction
+                .fees
+                .as_ref()
+                .map(|fees| fees.max_priority_fee_per_gas.clone()),
+            gas_price: None,
+            psbt: None,
+            external_id: transaction.external_id.clone(),
+        },
+    };
+
+    let response = client
+        .wallets()
+        .broadcast_transaction(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(response.id)
+}
+
+pub async fn transaction_status(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    transaction_id: &str,
+) -> Result<TransactionStatus, String> {
+    let response = client
+        .wallets()
+        .get_transaction(GetTransactionRequest {
+            wallet_id: wallet_id.to_string(),
+            transaction_id: transaction_id.to_string(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let details = serde_json::to_value(&response).map_err(|e| e.to_string())?;
+
+    Ok(TransactionStatus {
+        status: json_text(&details["status"]).unwrap_or_default(),
+        tx_hash: json_text(&details["txHash"]),
+        details,
+    })
+}
+
+pub async fn wait_for_transaction(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    transaction_id: &str,
+    timeout: Duration,
+) -> Result<TransactionStatus, String> {
+    let started = Instant::now();
+    loop {
+        let status = transaction_status(client, wallet_id, transaction_id).await?;
+        if status.is_terminal() {
+            return Ok(status);
+        }
+        if started.elapsed() >= timeout {
+            return Err(format!(
+                "transaction {} still {} after {}s",
+                transaction_id,
+                status.status,
+                timeout.as_secs()
+            ));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+pub fn intrinsic_gas(data: &[u8], create: bool) -> u64 {
+    let zero = data.iter().filter(|byte| **byte == 0).count() as u64;
+    let non_zero = data.len() as u64 - zero;
+    let mut gas = TX_GAS + zero * TX_DATA_ZERO_GAS + non_zero * TX_DATA_NON_ZERO_GAS;
+    if create {
+        gas += TX_CREATE_GAS + (data.len() as u64).div_ceil(32) * INIT_CODE_WORD_GAS;
+    }
+    gas
+}
+
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    match bytes {
+        [byte] if *byte < 0x80 => vec![*byte],
+        _ => {
+            let mut encoded = vec![0x80 + bytes.len() as u8];
+            encoded.extend_from_slice(bytes);
+            encoded
+        }
+    }
+}
+
+pub fn contract_address(sender: &[u8; 20], nonce: u64) -> [u8; 20] {
+    let nonce_bytes = nonce.to_be_bytes();
+    let start = nonce_bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(nonce_bytes.len());
+
+    let mut payload = rlp_bytes(sender);
+    payload.extend(rlp_bytes(&nonce_bytes[start..]));
+    let mut encoded = vec![0xc0 + payload.len() as u8];
+    encoded.extend(payload);
+
+    abi::keccak256(&encoded)[12..].try_into().unwrap()
+}
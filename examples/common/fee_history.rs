@@ -0,0 +1,311 @@
+#![allow(dead_code)]
+
+use crate::evm;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        networks::types::{GetFeesQueryNetwork, GetFeesRequest, Query},
+        wallets::types::Priority,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const TIERS: &[&str] = &["slow", "standard", "fast"];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FeeTier {
+    pub block_horizon: Option<u64>,
+    pub fee_rate: Option<String>,
+    pub max_fee_per_gas: Option<String>,
+    pub max_priority_fee_per_gas: Option<String>,
+}
+
+impl FeeTier {
+    pub fn fee(&self) -> Option<&str> {
+        self.max_fee_per_gas.as_deref().or(self.fee_rate.as_deref())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeeSample {
+    pub timestamp: u64,
+    pub network: String,
+    pub block_number: Option<u64>,
+    pub kind: Option<String>,
+    pub estimated_base_fee: Option<String>,
+    pub tiers: BTreeMap<String, FeeTier>,
+}
+
+impl FeeSample {
+    pub fn tier(&self, tier: &str) -> Option<&FeeTier> {
+        self.tiers.get(tier)
+    }
+}
+
+pub struct Recommendation {
+    pub priority: Priority,
+    pub tier: &'static str,
+    pub expected_wait: Option<Duration>,
+    pub block_time: Option<f64>,
+    pub current_fee: Option<String>,
+    pub median_fee: Option<String>,
+    pub fee: Option<String>,
+    pub priority_fee: Option<String>,
+    pub samples: usize,
+}
+
+pub struct FeeStore {
+    path: PathBuf,
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+pub fn parse_network(name: &str) -> Result<GetFeesQueryNetwork, String> {
+    evm::convert_network(&name)
+}
+
+pub fn network_name(network: &GetFeesQueryNetwork) -> String {
+    serde_json::to_value(network)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", network))
+}
+
+pub fn priority(tier: &str) -> Priority {
+    match tier {
+        "slow" => Priority::Slow,
+        "fast" => Priority::Fast,
+        _ => Priority::Standard,
+    }
+}
+
+fn numeric(value: &str) -> Option<f64> {
+    value.parse().ok()
+}
+
+pub async fn sample(
+    client: &DfnsApiClient,
+    network: GetFeesQueryNetwork,
+) -> Result<FeeSample, String> {
+    let name = network_name(&network);
+    let response = client
+        .networks()
+        .get_fees(Some(GetFeesRequest {
+            query: Some(Query { network }),
+        }))
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+
+    let tier =
+        |block_horizon: Option<f64>, fee_rate, max_fee_per_gas, max_priority_fee_per_gas| FeeTier {
+            block_horizon: block_horizon.map(|blocks| blocks as u64),
+            fee_rate,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        };
+    let (slow, standard, fast) = (response.slow, response.standard, response.fast);
+    let tiers = BTreeMap::from([
+        (
+            "slow".to_string(),
+            tier(
+                slow.block_horizon,
+                slow.fee_rate,
+                slow.max_fee_per_gas,
+                slow.max_priority_fee_per_gas,
+            ),
+        ),
+        (
+            "standard".to_string(),
+            tier(
+                standard.block_horizon,
+                standard.fee_rate,
+                standard.max_fee_per_gas,
+                standard.max_priority_fee_per_gas,
+            ),
+        ),
+        (
+            "fast".to_string(),
+            tier(
+                fast.block_horizon,
+                fast.fee_rate,
+                fast.max_fee_per_gas,
+                fast.max_priority_fee_per_gas,
+            ),
+        ),
+    ]);
+
+    Ok(FeeSample {
+        timestamp: unix_now(),
+        network: name,
+        block_number: Some(response.block_number as u64),
+        kind: Some(format!("{:?}", response.kind)),
+        estimated_base_fee: response.estimated_base_fee.map(|fee| fee.to_string()),
+        tiers,
+    })
+}
+
+impl FeeStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn append(&self, sample: &FeeSample) -> Result<(), String> {
+        let line = serde_json::to_string(sample).map_err(|e| e.to_string())?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("failed to open {}: {}", self.path.display(), e))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| format!("failed to write {}: {}", self.path.display(), e))
+    }
+
+    pub fn load(&self, network: &str, since: u64) -> Result<Vec<FeeSample>, String> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut samples: Vec<FeeSample> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<FeeSample>(line).ok())
+            .filter(|sample| sample.network.eq_ignore_ascii_case(network))
+            .filter(|sample| sample.timestamp >= since)
+            .collect();
+        samples.sort_by_key(|sample| sample.timestamp);
+        Ok(samples)
+    }
+
+    pub fn prune(&self, before: u64) -> Result<usize, String> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(_) => return Ok(0),
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let kept: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|line| {
+                serde_json::from_str::<FeeSample>(line)
+                    .map(|sample| sample.timestamp >= before)
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let mut output = kept.join("\n");
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        fs::write(&self.path, output)
+            .map_err(|e| format!("failed to write {}: {}", self.path.display(), e))?;
+        Ok(lines.len() - kept.len())
+    }
+}
+
+pub fn block_time(samples: &[FeeSample]) -> Option<f64> {
+    let with_blocks: Vec<_> = samples
+        .iter()
+        .filter_map(|sample| Some((sample.timestamp, sample.block_number?)))
+        .collect();
+    let (first_time, first_block) = *with_blocks.first()?;
+    let (last_time, last_block) = *with_blocks.last()?;
+
+    if last_block <= first_block || last_time <= first_time {
+        return None;
+    }
+    Some((last_time - first_time) as f64 / (last_block - first_block) as f64)
+}
+
+pub fn percentile<'a>(values: impl IntoIterator<Item = &'a str>, percent: u8) -> Option<String> {
+    let mut values: Vec<(f64, &str)> = values
+        .into_iter()
+        .filter_map(|value| Some((numeric(value)?, value)))
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_by(|left, right| left.0.total_cmp(&right.0));
+    let rank = (values.len() - 1) * percent.min(100) as usize / 100;
+    Some(values[rank].1.to_string())
+}
+
+fn higher(left: Option<String>, right: Option<String>) -> Option<String> {
+    match (left, right) {
+        (Some(left), Some(right)) => match (numeric(&left), numeric(&right)) {
+            (Some(a), Some(b)) if b > a => Some(right),
+            _ => Some(left),
+        },
+        (left, right) => left.or(right),
+    }
+}
+
+pub fn recommend(
+    samples: &[FeeSample],
+    window: Duration,
+    percent: u8,
+    fallback_block_time: Option<f64>,
+) -> Result<Recommendation, String> {
+    let latest = samples
+        .last()
+        .ok_or("no fee samples recorded for this network")?;
+    let block_time = block_time(samples).or(fallback_block_time);
+
+    let expected_wait = |tier: &str| {
+        let horizon = latest.tier(tier)?.block_horizon?;
+        Duration::try_from_secs_f64(horizon as f64 * block_time?).ok()
+    };
+
+    let tier = TIERS
+        .iter()
+        .copied()
+        .filter(|tier| latest.tier(tier).is_some())
+        .find(|tier| expected_wait(tier).is_some_and(|wait| wait <= window))
+        .or_else(|| {
+            TIERS
+                .iter()
+                .rev()
+                .copied()
+                .find(|tier| latest.tier(tier).is_some())
+        })
+        .ok_or("latest fee sample has no fee tiers")?;
+
+    let history = |field: fn(&FeeTier) -> Option<&str>, percent: u8| {
+        percentile(
+            samples
+                .iter()
+                .filter_map(|sample| sample.tier(tier).and_then(field)),
+            percent,
+        )
+    };
+    let current = latest.tier(tier);
+    let current_fee = current.and_then(FeeTier::fee).map(str::to_string);
+
+    Ok(Recommendation {
+        priority: priority(tier),
+        tier,
+        expected_wait: expected_wait(tier),
+        block_time,
+        median_fee: history(FeeTier::fee, 50),
+        fee: higher(current_fee.clone(), history(FeeTier::fee, percent)),
+        priority_fee: higher(
+            current.and_then(|tier| tier.max_priority_fee_per_gas.clone()),
+            history(|tier| tier.max_priority_fee_per_gas.as_deref(), percent),
+        ),
+        current_fee,
+        samples: samples.len(),
+    })
+}
@@ -0,0 +1,162 @@
+#![allow(dead_code)]
+
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce, aead::Aead};
+use hkdf::Hkdf;
+use k256::{
+    NonZeroScalar, PublicKey, Scalar, SecretKey,
+    ecdh::{EphemeralSecret, diffie_hellman},
+    elliptic_curve::{Field, PrimeField, sec1::ToEncodedPoint},
+};
+use rand::{RngCore, rngs::OsRng};
+use sha2::Sha256;
+
+pub const DEMO_FORMAT_NOTICE: &str = "Key shares use a demo Shamir/ECIES envelope defined in \
+examples/common/key_shares.rs; it is not the Dfns import/export format and Dfns signers \
+cannot read it. Use the official Dfns key import/export utilities for real wallets.";
+
+const HKDF_INFO: &[u8] = b"dfns-sdk-rs-examples/demo-key-share";
+const COMPRESSED_POINT_LEN: usize = 33;
+const NONCE_LEN: usize = 12;
+const SHARE_LEN: usize = 36;
+
+pub struct KeyShare {
+    pub index: u32,
+    pub value: Scalar,
+}
+
+impl KeyShare {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SHARE_LEN);
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&self.value.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != SHARE_LEN {
+            return Err(format!(
+                "key share must be {} bytes, got {}",
+                SHARE_LEN,
+                bytes.len()
+            ));
+        }
+
+        let index = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+        let repr = k256::FieldBytes::clone_from_slice(&bytes[4..]);
+        let value = Option::from(Scalar::from_repr(repr))
+            .ok_or_else(|| "key share is not a valid scalar".to_string())?;
+
+        Ok(Self { index, value })
+    }
+}
+
+pub fn parse_secret_key(encoded: &str) -> Result<SecretKey, String> {
+    let bytes = hex::decode(encoded.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    SecretKey::from_slice(&bytes).map_err(|_| "invalid secp256k1 private key".to_string())
+}
+
+pub fn parse_public_key(encoded: &str) -> Result<PublicKey, String> {
+    let bytes = hex::decode(encoded.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    PublicKey::from_sec1_bytes(&bytes).map_err(|_| "invalid secp256k1 public key".to_string())
+}
+
+pub fn compressed_public_key(secret: &SecretKey) -> String {
+    hex::encode(secret.public_key().to_encoded_point(true).as_bytes())
+}
+
+pub fn split_secret(
+    secret: &SecretKey,
+    threshold: usize,
+    count: usize,
+) -> Result<Vec<KeyShare>, String> {
+    if threshold < 2 || threshold > count {
+        return Err(format!(
+            "threshold must be between 2 and the number of shares ({}), got {}",
+            count, threshold
+        ));
+    }
+
+    let mut coefficients = vec![*secret.to_nonzero_scalar()];
+    coefficients.extend((1..threshold).map(|_| Scalar::random(&mut OsRng)));
+
+    Ok((1..=count as u32)
+        .map(|index| {
+            let x = Scalar::from(index as u64);
+            let value = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::ZERO, |acc, coefficient| acc * x + coefficient);
+            KeyShare { index, value }
+        })
+        .collect())
+}
+
+pub fn reconstruct_secret(shares: &[KeyShare]) -> Result<SecretKey, String> {
+    if shares.is_empty() {
+        return Err("no key shares to reconstruct from".to_string());
+    }
+
+    let mut secret = Scalar::ZERO;
+    for share in shares {
+        let xi = Scalar::from(share.index as u64);
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+
+        for other in shares.iter().filter(|other| other.index != share.index) {
+            let xj = Scalar::from(other.index as u64);
+            numerator *= xj;
+            denominator *= xj - xi;
+        }
+
+        let inverse = Option::<Scalar>::from(denominator.invert())
+            .ok_or_else(|| format!("duplicate key share index {}", share.index))?;
+        secret += share.value * numerator * inverse;
+    }
+
+    Option::<NonZeroScalar>::from(NonZeroScalar::new(secret))
+        .map(SecretKey::from)
+        .ok_or_else(|| "reconstructed key is zero".to_string())
+}
+
+pub fn encrypt(recipient: &PublicKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let ephemeral = EphemeralSecret::random(&mut OsRng);
+    let ephemeral_public = ephemeral.public_key().to_encoded_point(true);
+    let shared = ephemeral.diffie_hellman(recipient);
+    let cipher = cipher(shared.raw_secret_bytes(), ephemeral_public.as_bytes())?;
+
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "encryption failed".to_string())?;
+
+    let mut payload = Vec::with_capacity(COMPRESSED_POINT_LEN + NONCE_LEN + ciphertext.len());
+    payload.extend_from_slice(ephemeral_public.as_bytes());
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&ciphertext);
+    Ok(payload)
+}
+
+pub fn decrypt(secret: &SecretKey, payload: &[u8]) -> Result<Vec<u8>, String> {
+    if payload.len() < COMPRESSED_POINT_LEN + NONCE_LEN {
+        return Err("encrypted payload is too short".to_string());
+    }
+
+    let (ephemeral_public, rest) = payload.split_at(COMPRESSED_POINT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let ephemeral = PublicKey::from_sec1_bytes(ephemeral_public)
+        .map_err(|_| "invalid ephemeral public key".to_string())?;
+
+    let shared = diffie_hellman(secret.to_nonzero_scalar(), ephemeral.as_affine());
+    cipher(shared.raw_secret_bytes(), ephemeral_public)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "decryption failed".to_string())
+}
+
+fn cipher(shared_secret: &[u8], salt: &[u8]) -> Result<Aes256Gcm, String> {
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(salt), shared_secret)
+        .expand(HKDF_INFO, &mut key)
+        .map_err(|e| e.to_string())?;
+    Aes256Gcm::new_from_slice(&key).map_err(|e| e.to_string())
+}
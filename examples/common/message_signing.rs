@@ -0,0 +1,372 @@
+#![allow(dead_code)]
+
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+const EIP191_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+const BITCOIN_MESSAGE_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+const SOLANA_OFFCHAIN_DOMAIN: &[u8] = b"\xffsolana offchain";
+const SOLANA_MAX_LEDGER_MESSAGE_LEN: usize = 1212;
+const SIGHASH_ALL: u32 = 1;
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+pub fn hex_prefixed(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+pub fn parse_signature(r: &str, s: &str) -> Result<Signature, String> {
+    let mut bytes = [0u8; 64];
+    for (offset, part) in [(0, r), (32, s)] {
+        let decoded = hex::decode(part.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+        if decoded.len() > 32 {
+            return Err("signature component is longer than 32 bytes".to_string());
+        }
+        bytes[offset + 32 - decoded.len()..offset + 32].copy_from_slice(&decoded);
+    }
+    Signature::from_slice(&bytes).map_err(|e| e.to_string())
+}
+
+pub fn recover(
+    prehash: &[u8; 32],
+    signature: &Signature,
+    recid: u8,
+) -> Result<VerifyingKey, String> {
+    let recovery_id = RecoveryId::from_byte(recid).ok_or("invalid recovery id")?;
+    VerifyingKey::recover_from_prehash(prehash, signature, recovery_id).map_err(|e| e.to_string())
+}
+
+pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = format!("{}{}", EIP191_PREFIX, message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
+pub fn eth_address(key: &VerifyingKey) -> String {
+    let uncompressed = key.to_encoded_point(false);
+    hex_prefixed(&keccak256(&uncompressed.as_bytes()[1..])[12..])
+}
+
+pub fn eth_signature(signature: &Signature, recid: u8) -> String {
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(27 + recid);
+    hex_prefixed(&bytes)
+}
+
+pub fn verify_eip191(
+    message: &[u8],
+    signature: &Signature,
+    recid: u8,
+    address: &str,
+) -> Result<bool, String> {
+    let key = recover(&eip191_hash(message), signature, recid)?;
+    Ok(eth_address(&key).eq_ignore_ascii_case(address))
+}
+
+pub fn utc_timestamp(unix: u64) -> String {
+    let days = (unix / 86_400) as i64 + 719_468;
+    let seconds = unix % 86_400;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    pub fn to_message(&self) -> Result<String, String> {
+        if self.nonce.len() < 8 || !self.nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err("SIWE nonce must be at least 8 alphanumeric characters".to_string());
+        }
+
+        let mut lines = vec![
+            format!(
+                "{} wants you to sign in with your Ethereum account:",
+                self.domain
+            ),
+            self.address.clone(),
+            String::new(),
+        ];
+        if let Some(statement) = &self.statement {
+            lines.push(statement.clone());
+            lines.push(String::new());
+        }
+
+        lines.push(format!("URI: {}", self.uri));
+        lines.push("Version: 1".to_string());
+        lines.push(format!("Chain ID: {}", self.chain_id));
+        lines.push(format!("Nonce: {}", self.nonce));
+        lines.push(format!("Issued At: {}", self.issued_at));
+        if let Some(expiration_time) = &self.expiration_time {
+            lines.push(format!("Expiration Time: {}", expiration_time));
+        }
+        if let Some(not_before) = &self.not_before {
+            lines.push(format!("Not Before: {}", not_before));
+        }
+        if let Some(request_id) = &self.request_id {
+            lines.push(format!("Request ID: {}", request_id));
+        }
+        if !self.resources.is_empty() {
+            lines.push("Resources:".to_string());
+            lines.extend(
+                self.resources
+                    .iter()
+                    .map(|resource| format!("- {}", resource)),
+            );
+        }
+
+        Ok(lines.join("\n"))
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, value: usize) {
+    match value {
+        0..=0xfc => out.push(value as u8),
+        0xfd..=0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+        0x10000..=0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+        _ => {
+            out.push(0xff);
+            out.extend_from_slice(&(value as u64).to_le_bytes());
+        }
+    }
+}
+
+fn write_var_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+pub enum Bip137AddressKind {
+    P2pkhCompressed,
+    P2shP2wpkh,
+    P2wpkh,
+}
+
+impl Bip137AddressKind {
+    fn header_offset(&self) -> u8 {
+        match self {
+            Bip137AddressKind::P2pkhCompressed => 31,
+            Bip137AddressKind::P2shP2wpkh => 35,
+            Bip137AddressKind::P2wpkh => 39,
+        }
+    }
+}
+
+pub fn bip137_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = BITCOIN_MESSAGE_PREFIX.to_vec();
+    write_var_bytes(&mut data, message);
+    sha256d(&data)
+}
+
+pub fn bip137_signature(signature: &Signature, recid: u8, kind: &Bip137AddressKind) -> String {
+    use base64::Engine;
+
+    let mut bytes = vec![kind.header_offset() + recid];
+    bytes.extend_from_slice(&signature.to_bytes());
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+pub fn verify_bip137(message: &[u8], encoded: &str, public_key: &[u8]) -> Result<bool, String> {
+    use base64::Engine;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| e.to_string())?;
+    if bytes.len() != 65 || !(27..=42).contains(&bytes[0]) {
+        return Err("invalid BIP-137 signature".to_string());
+    }
+
+    let recid = (bytes[0] - 27) % 4;
+    let signature = Signature::from_slice(&bytes[1..]).map_err(|e| e.to_string())?;
+    let key = recover(&bip137_hash(message), &signature, recid)?;
+    Ok(key.to_encoded_point(true).as_bytes() == public_key)
+}
+
+pub fn bip322_message_hash(message: &[u8]) -> [u8; 32] {
+    let tag = Sha256::digest(BIP322_TAG);
+    Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(message)
+        .finalize()
+        .into()
+}
+
+pub struct Bip322P2wpkh {
+    pub public_key: Vec<u8>,
+    to_spend_txid: [u8; 32],
+}
+
+impl Bip322P2wpkh {
+    pub fn new(message: &[u8], public_key: &[u8]) -> Self {
+        let mut script_sig = vec![0x00, 0x20];
+        script_sig.extend_from_slice(&bip322_message_hash(message));
+
+        let mut to_spend = Vec::new();
+        to_spend.extend_from_slice(&0u32.to_le_bytes());
+        write_varint(&mut to_spend, 1);
+        to_spend.extend_from_slice(&[0u8; 32]);
+        to_spend.extend_from_slice(&u32::MAX.to_le_bytes());
+        write_var_bytes(&mut to_spend, &script_sig);
+        to_spend.extend_from_slice(&0u32.to_le_bytes());
+        write_varint(&mut to_spend, 1);
+        to_spend.extend_from_slice(&0u64.to_le_bytes());
+        write_var_bytes(&mut to_spend, &Self::script_pubkey(public_key));
+        to_spend.extend_from_slice(&0u32.to_le_bytes());
+
+        Self {
+            public_key: public_key.to_vec(),
+            to_spend_txid: sha256d(&to_spend),
+        }
+    }
+
+    fn script_pubkey(public_key: &[u8]) -> Vec<u8> {
+        let mut script = vec![0x00, 0x14];
+        script.extend_from_slice(&hash160(public_key));
+        script
+    }
+
+    pub fn sighash(&self) -> [u8; 32] {
+        let mut outpoint = self.to_spend_txid.to_vec();
+        outpoint.extend_from_slice(&0u32.to_le_bytes());
+        let sequence = 0u32.to_le_bytes();
+
+        let mut script_code = vec![0x76, 0xa9, 0x14];
+        script_code.extend_from_slice(&hash160(&self.public_key));
+        script_code.extend_from_slice(&[0x88, 0xac]);
+
+        let mut output = 0u64.to_le_bytes().to_vec();
+        write_var_bytes(&mut output, &[0x6a]);
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&0u32.to_le_bytes());
+        preimage.extend_from_slice(&sha256d(&outpoint));
+        preimage.extend_from_slice(&sha256d(&sequence));
+        preimage.extend_from_slice(&outpoint);
+        write_var_bytes(&mut preimage, &script_code);
+        preimage.extend_from_slice(&0u64.to_le_bytes());
+        preimage.extend_from_slice(&sequence);
+        preimage.extend_from_slice(&sha256d(&output));
+        preimage.extend_from_slice(&0u32.to_le_bytes());
+        preimage.extend_from_slice(&SIGHASH_ALL.to_le_bytes());
+        sha256d(&preimage)
+    }
+
+    pub fn encode_signature(&self, signature: &Signature) -> String {
+        use base64::Engine;
+
+        let signature = signature.normalize_s().unwrap_or(*signature);
+        let mut der = signature.to_der().as_bytes().to_vec();
+        der.push(SIGHASH_ALL as u8);
+
+        let mut witness = Vec::new();
+        write_varint(&mut witness, 2);
+        write_var_bytes(&mut witness, &der);
+        write_var_bytes(&mut witness, &self.public_key);
+        base64::engine::general_purpose::STANDARD.encode(witness)
+    }
+
+    pub fn verify(&self, signature: &Signature) -> Result<bool, String> {
+        use k256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        let key = VerifyingKey::from_sec1_bytes(&self.public_key).map_err(|e| e.to_string())?;
+        let signature = signature.normalize_s().unwrap_or(*signature);
+        Ok(key.verify_prehash(&self.sighash(), &signature).is_ok())
+    }
+}
+
+pub fn solana_offchain_message(
+    message: &[u8],
+    application_domain: [u8; 32],
+    signers: &[[u8; 32]],
+) -> Result<Vec<u8>, String> {
+    if message.is_empty() || message.len() > u16::MAX as usize {
+        return Err("message must be between 1 and 65535 bytes".to_string());
+    }
+
+    let format = if std::str::from_utf8(message).is_err() {
+        return Err("off-chain messages must be valid UTF-8".to_string());
+    } else if message.len() > SOLANA_MAX_LEDGER_MESSAGE_LEN {
+        2
+    } else if message.iter().all(|b| (0x20..=0x7e).contains(b)) {
+        0
+    } else {
+        1
+    };
+
+    let mut out = SOLANA_OFFCHAIN_DOMAIN.to_vec();
+    out.push(0);
+    out.extend_from_slice(&application_domain);
+    out.push(format);
+    out.push(u8::try_from(signers.len()).map_err(|_| "too many signers")?);
+    for signer in signers {
+        out.extend_from_slice(signer);
+    }
+    out.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    out.extend_from_slice(message);
+    Ok(out)
+}
+
+pub fn verify_ed25519(message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, String> {
+    let public_key: [u8; 32] = public_key
+        .try_into()
+        .map_err(|_| "ed25519 public key must be 32 bytes")?;
+    let signature: [u8; 64] = signature
+        .try_into()
+        .map_err(|_| "ed25519 signature must be 64 bytes")?;
+    let key = ed25519_dalek::VerifyingKey::from_bytes(&public_key).map_err(|e| e.to_string())?;
+    Ok(key
+        .verify_strict(message, &ed25519_dalek::Signature::from_bytes(&signature))
+        .is_ok())
+}
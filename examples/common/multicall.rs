@@ -0,0 +1,190 @@
+#![allow(dead_code)]
+
+use crate::{
+    abi::{self, Function, ParamType, Token},
+    evm,
+};
+use dfns_sdk_rs::{DfnsApiClient, api::networks::types::ReadContractBodyNetwork};
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+pub const DEFAULT_CHUNK_SIZE: usize = 200;
+
+const AGGREGATE3: &str = "aggregate3((address,bool,bytes)[])((bool,bytes)[])";
+const CHUNK_CONCURRENCY: usize = 4;
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+#[derive(Clone)]
+pub struct Call {
+    pub target: String,
+    pub data: Vec<u8>,
+    pub allow_failure: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallResult {
+    pub success: bool,
+    pub data: Vec<u8>,
+}
+
+pub fn revert_reason(data: &[u8]) -> String {
+    if data.is_empty() {
+        return "reverted without reason".to_string();
+    }
+    let (selector, payload) = data.split_at(data.len().min(4));
+    if selector == ERROR_SELECTOR
+        && let Ok(Some(reason)) = abi::decode(&[ParamType::String], payload)
+            .map(|tokens| tokens.into_iter().next().and_then(Token::into_string))
+    {
+        return format!("reverted: {}", reason);
+    }
+    if selector == PANIC_SELECTOR
+        && let Ok(Some(code)) = abi::decode(&[ParamType::Uint(256)], payload)
+            .map(|tokens| tokens.into_iter().next().and_then(Token::into_uint))
+    {
+        return format!("panicked with code 0x{:x}", code);
+    }
+    format!("reverted with data 0x{}", hex::encode(data))
+}
+
+async fn aggregate3_chunk(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    calls: &[Call],
+) -> Result<Vec<CallResult>, String> {
+    let function = Function::parse(AGGREGATE3)?;
+    let encoded = calls
+        .iter()
+        .map(|call| {
+            Ok(Token::Tuple(vec![
+                Token::Address(abi::parse_address(&call.target)?),
+                Token::Bool(call.allow_failure),
+                Token::Bytes(call.data.clone()),
+            ]))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let outputs = evm::call(
+        client,
+        network,
+        MULTICALL3_ADDRESS,
+        &function,
+        &[Token::Array(encoded)],
+    )
+    .await?;
+
+    let results = outputs
+        .into_iter()
+        .next()
+        .and_then(Token::into_array)
+        .ok_or("aggregate3 returned no results")?;
+    if results.len() != calls.len() {
+        return Err(format!(
+            "aggregate3 returned {} results for {} calls",
+            results.len(),
+            calls.len()
+        ));
+    }
+
+    results
+        .into_iter()
+        .map(|result| {
+            let mut fields = result.into_array().unwrap_or_default().into_iter();
+            match (
+                fields.next().and_then(Token::into_bool),
+                fields.next().and_then(Token::into_bytes),
+            ) {
+                (Some(success), Some(data)) => Ok(CallResult { success, data }),
+                _ => Err("aggregate3 returned a malformed result".to_string()),
+            }
+        })
+        .collect()
+}
+
+pub async fn aggregate3(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    calls: &[Call],
+    chunk_size: usize,
+) -> Result<Vec<CallResult>, String> {
+    let chunks: Vec<Vec<CallResult>> = stream::iter(calls.chunks(chunk_size.max(1)))
+        .map(|chunk| aggregate3_chunk(client, network, chunk))
+        .buffered(CHUNK_CONCURRENCY)
+        .try_collect()
+        .await?;
+    Ok(chunks.into_iter().flatten().collect())
+}
+
+pub struct Batch {
+    calls: Vec<Call>,
+    functions: Vec<Function>,
+    chunk_size: usize,
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Batch {
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            calls: Vec::new(),
+            functions: Vec::new(),
+            chunk_size,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    pub fn add(
+        &mut self,
+        target: &str,
+        function: &Function,
+        args: &[Token],
+    ) -> Result<usize, String> {
+        self.calls.push(Call {
+            target: target.to_string(),
+            data: function.encode_input(args)?,
+            allow_failure: true,
+        });
+        self.functions.push(function.clone());
+        Ok(self.calls.len() - 1)
+    }
+
+    pub async fn execute_raw(
+        &self,
+        client: &DfnsApiClient,
+        network: &ReadContractBodyNetwork,
+    ) -> Result<Vec<CallResult>, String> {
+        aggregate3(client, network, &self.calls, self.chunk_size).await
+    }
+
+    pub async fn execute(
+        &self,
+        client: &DfnsApiClient,
+        network: &ReadContractBodyNetwork,
+    ) -> Result<Vec<Result<Vec<Token>, String>>, String> {
+        let results = self.execute_raw(client, network).await?;
+        Ok(results
+            .into_iter()
+            .zip(&self.functions)
+            .map(|(result, function)| match result.success {
+                true => function.decode_output(&result.data),
+                false => Err(revert_reason(&result.data)),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,410 @@
+#![allow(dead_code)]
+
+use crate::{
+    abi,
+    evm::{self, Eip1559Fees, EvmTransaction},
+};
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::{ListTransactionsRequest, ListTransactionsRequestQuery},
+};
+use ruint::aliases::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const PENDING_STATUSES: &[&str] = &["Pending", "Executing", "Broadcasted"];
+const MIN_REPLACEMENT_BUMP: u64 = 10;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrackedTransaction {
+    pub id: String,
+    pub nonce: u64,
+    pub status: String,
+    pub tx_hash: Option<String>,
+    pub submitted_at: u64,
+    pub to: Option<String>,
+    pub data: String,
+    pub value: String,
+    pub gas_limit: Option<u64>,
+    pub max_fee_per_gas: Option<String>,
+    pub max_priority_fee_per_gas: Option<String>,
+    pub replaced_by: Option<String>,
+}
+
+impl TrackedTransaction {
+    pub fn is_pending(&self) -> bool {
+        PENDING_STATUSES.contains(&self.status.as_str())
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.status == "Confirmed"
+    }
+
+    fn holds_nonce(&self) -> bool {
+        match self.status.as_str() {
+            "Rejected" => false,
+            "Failed" => self.tx_hash.is_some(),
+            _ => true,
+        }
+    }
+
+    fn fees(&self) -> Option<Eip1559Fees> {
+        Some(Eip1559Fees {
+            max_fee_per_gas: self.max_fee_per_gas.clone()?,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.clone()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WalletNonces {
+    pub confirmed_nonce: Option<u64>,
+    pub transactions: BTreeMap<String, TrackedTransaction>,
+    #[serde(skip)]
+    pub untracked_nonces: BTreeSet<u64>,
+    #[serde(skip)]
+    pub unknown_nonce_pending: Vec<String>,
+}
+
+impl WalletNonces {
+    fn first_unconfirmed(&self) -> u64 {
+        self.confirmed_nonce.map(|nonce| nonce + 1).unwrap_or(0)
+    }
+
+    fn held_nonces(&self) -> BTreeSet<u64> {
+        self.transactions
+            .values()
+            .filter(|tx| tx.holds_nonce())
+            .map(|tx| tx.nonce)
+            .chain(self.untracked_nonces.iter().copied())
+            .collect()
+    }
+
+    pub fn next_nonce(&self) -> u64 {
+        let held = self.held_nonces();
+        (self.first_unconfirmed()..)
+            .find(|nonce| !held.contains(nonce))
+            .unwrap_or_default()
+    }
+
+    pub fn gaps(&self) -> Vec<u64> {
+        let held = self.held_nonces();
+        let Some(highest) = held.last().copied() else {
+            return Vec::new();
+        };
+        (self.first_unconfirmed()..highest)
+            .filter(|nonce| !held.contains(nonce))
+            .collect()
+    }
+
+    pub fn pending(&self) -> Vec<&TrackedTransaction> {
+        let mut pending: Vec<_> = self
+            .transactions
+            .values()
+            .filter(|tx| tx.is_pending() && tx.replaced_by.is_none())
+            .collect();
+        pending.sort_by_key(|tx| tx.nonce);
+        pending
+    }
+
+    pub fn stuck(&self, threshold: Duration) -> Vec<&TrackedTransaction> {
+        let now = unix_now();
+        self.pending()
+            .into_iter()
+            .filter(|tx| now.saturating_sub(tx.submitted_at) >= threshold.as_secs())
+            .collect()
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct NonceManager {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    wallets: BTreeMap<String, WalletNonces>,
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn request_nonce(value: &Value) -> Option<u64> {
+    value
+        .as_f64()
+        .map(|nonce| nonce as u64)
+        .or_else(|| value.as_str()?.parse().ok())
+}
+
+fn bump(value: &str, percent: u64) -> Result<String, String> {
+    let value = abi::parse_uint(value)?;
+    let bumped = value * U256::from(100 + percent.max(MIN_REPLACEMENT_BUMP)) / U256::from(100);
+    Ok(bumped.max(value + U256::from(1)).to_string())
+}
+
+fn max_fee(left: &str, right: &str) -> Result<String, String> {
+    Ok(abi::parse_uint(left)?
+        .max(abi::parse_uint(right)?)
+        .to_string())
+}
+
+impl NonceManager {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let mut manager = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("failed to parse {}: {}", path.display(), e))?,
+            Err(_) => NonceManager::default(),
+        };
+        manager.path = Some(path);
+        Ok(manager)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn wallet(&self, wallet_id: &str) -> Option<&WalletNonces> {
+        self.wallets.get(wallet_id)
+    }
+
+    pub fn set_confirmed_nonce(&mut self, wallet_id: &str, nonce: u64) {
+        let wallet = self.wallets.entry(wallet_id.to_string()).or_default();
+        wallet.confirmed_nonce = wallet.confirmed_nonce.max(Some(nonce));
+    }
+
+    pub async fn sync(
+        &mut self,
+        client: &DfnsApiClient,
+        wallet_id: &str,
+        max_pages: usize,
+    ) -> Result<(), String> {
+        let wallet = self.wallets.entry(wallet_id.to_string()).or_default();
+        wallet.untracked_nonces.clear();
+        wallet.unknown_nonce_pending.clear();
+        let mut pagination_token = None;
+
+        for _ in 0..max_pages {
+            let response = client
+                .wallets()
+                .list_transactions(ListTransactionsRequest {
+                    wallet_id: wallet_id.to_string(),
+                    query: Some(ListTransactionsRequestQuery {
+                        limit: Some("100".to_string()),
+                        pagination_token: pagination_token.take(),
+                    }),
+                })
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            let page = serde_json::to_value(&response).map_err(|e| e.to_string())?;
+
+            for item in page["items"].as_array().into_iter().flatten() {
+                let Some(id) = evm::json_text(&item["id"]) else {
+                    continue;
+                };
+                let status = evm::json_text(&item["status"]).unwrap_or_default();
+                let tx_hash = evm::json_text(&item["txHash"]);
+                let nonce = request_nonce(&item["requestBody"]["nonce"]);
+
+                match wallet.transactions.get_mut(&id) {
+                    Some(tracked) => {
+                        tracked.status = status.clone();
+                        tracked.tx_hash = tx_hash.or(tracked.tx_hash.take());
+                    }
+                    None if PENDING_STATUSES.contains(&status.as_str()) => match nonce {
+                        Some(nonce) => {
+                            wallet.untracked_nonces.insert(nonce);
+                        }
+                        None => wallet.unknown_nonce_pending.push(id.clone()),
+                    },
+                    None => {}
+                }
+                let known_nonce = nonce.or_else(|| wallet.transactions.get(&id).map(|tx| tx.nonce));
+                if let (Some(nonce), "Confirmed") = (known_nonce, status.as_str()) {
+                    wallet.confirmed_nonce = wallet.confirmed_nonce.max(Some(nonce));
+                }
+            }
+
+            match page["nextPageToken"].as_str() {
+                Some(token) => pagination_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+
+        if let Some(confirmed) = wallet.confirmed_nonce {
+            for tracked in wallet.transactions.values_mut() {
+                if tracked.nonce <= confirmed && tracked.is_pending() {
+                    tracked.status = "Dropped".to_string();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn confirmed_nonce(&self, wallet_id: &str) -> Result<u64, String> {
+        self.wallet(wallet_id)
+            .and_then(|wallet| wallet.confirmed_nonce)
+            .ok_or_else(|| {
+                format!(
+                    "confirmed nonce of {} is unknown, pass --confirmed-nonce",
+                    wallet_id
+                )
+            })
+    }
+
+    pub fn ensure_known_nonces(&self, wallet_id: &str) -> Result<(), String> {
+        match self.wallet(wallet_id) {
+            Some(wallet) if !wallet.unknown_nonce_pending.is_empty() => Err(format!(
+                "pending transactions without a known nonce ({}), wait for them to confirm",
+                wallet.unknown_nonce_pending.join(", ")
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn reserve(&mut self, wallet_id: &str, count: usize) -> Result<Vec<u64>, String> {
+        self.confirmed_nonce(wallet_id)?;
+        self.ensure_known_nonces(wallet_id)?;
+        let wallet = self.wallets.entry(wallet_id.to_string()).or_default();
+        let held = wallet.held_nonces();
+        Ok((wallet.first_unconfirmed()..)
+            .filter(|nonce| !held.contains(nonce))
+            .take(count)
+            .collect())
+    }
+
+    pub fn record(
+        &mut self,
+        wallet_id: &str,
+        id: &str,
+        transaction: &EvmTransaction,
+    ) -> Result<(), String> {
+        let nonce = transaction
+            .nonce
+            .ok_or_else(|| format!("transaction {} has no nonce to track", id))?;
+        let wallet = self.wallets.entry(wallet_id.to_string()).or_default();
+        wallet.transactions.insert(
+            id.to_string(),
+            TrackedTransaction {
+                id: id.to_string(),
+                nonce,
+                status: "Pending".to_string(),
+                tx_hash: None,
+                submitted_at: unix_now(),
+                to: transaction.to.clone(),
+                data: format!("0x{}", hex::encode(&transaction.data)),
+                value: transaction.value.to_string(),
+                gas_limit: transaction.gas_limit,
+                max_fee_per_gas: transaction
+                    .fees
+                    .as_ref()
+                    .map(|fees| fees.max_fee_per_gas.clone()),
+                max_priority_fee_per_gas: transaction
+                    .fees
+                    .as_ref()
+                    .map(|fees| fees.max_priority_fee_per_gas.clone()),
+                replaced_by: None,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn mark_replaced(&mut self, wallet_id: &str, original: &str, replacement: &str) {
+        if let Some(tracked) = self
+            .wallets
+            .get_mut(wallet_id)
+            .and_then(|wallet| wallet.transactions.get_mut(original))
+        {
+            tracked.replaced_by = Some(replacement.to_string());
+        }
+    }
+
+    pub fn speed_up(
+        &self,
+        wallet_id: &str,
+        transaction_id: &str,
+        percent: u64,
+        current: &Eip1559Fees,
+    ) -> Result<EvmTransaction, String> {
+        let tracked = self.tracked(wallet_id, transaction_id)?;
+        Ok(EvmTransaction {
+            to: tracked.to.clone(),
+            data: hex::decode(tracked.data.trim_start_matches("0x")).map_err(|e| e.to_string())?,
+            value: abi::parse_uint(&tracked.value)?,
+            gas_limit: tracked.gas_limit,
+            nonce: Some(tracked.nonce),
+            fees: Some(replacement_fees(tracked, percent, current)?),
+            external_id: None,
+        })
+    }
+
+    pub fn cancel(
+        &self,
+        wallet_id: &str,
+        transaction_id: &str,
+        sender: &str,
+        percent: u64,
+        current: &Eip1559Fees,
+    ) -> Result<EvmTransaction, String> {
+        let tracked = self.tracked(wallet_id, transaction_id)?;
+        Ok(EvmTransaction {
+            to: Some(sender.to_string()),
+            data: Vec::new(),
+            value: U256::ZERO,
+            gas_limit: Some(21_000),
+            nonce: Some(tracked.nonce),
+            fees: Some(replacement_fees(tracked, percent, current)?),
+            external_id: None,
+        })
+    }
+
+    fn tracked(
+        &self,
+        wallet_id: &str,
+        transaction_id: &str,
+    ) -> Result<&TrackedTransaction, String> {
+        let tracked = self
+            .wallet(wallet_id)
+            .and_then(|wallet| wallet.transactions.get(transaction_id))
+            .ok_or_else(|| format!("transaction {} is not tracked", transaction_id))?;
+        if !tracked.is_pending() {
+            return Err(format!(
+                "transaction {} is {} and cannot be replaced",
+                transaction_id, tracked.status
+            ));
+        }
+        Ok(tracked)
+    }
+}
+
+fn replacement_fees(
+    tracked: &TrackedTransaction,
+    percent: u64,
+    current: &Eip1559Fees,
+) -> Result<Eip1559Fees, String> {
+    let Some(original) = tracked.fees() else {
+        return Ok(current.clone());
+    };
+    Ok(Eip1559Fees {
+        max_fee_per_gas: max_fee(
+            &bump(&original.max_fee_per_gas, percent)?,
+            &current.max_fee_per_gas,
+        )?,
+        max_priority_fee_per_gas: max_fee(
+            &bump(&original.max_priority_fee_per_gas, percent)?,
+            &current.max_priority_fee_per_gas,
+        )?,
+    })
+}
@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::policies::types::{ListPoliciesRequest, ListPoliciesRequestQuery},
+};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use std::fs;
+
+pub const MANAGED_FIELDS: &[&str] = &["activityKind", "rule", "action", "filters"];
+
+#[derive(Deserialize)]
+pub struct PolicyFile {
+    #[serde(default)]
+    pub policies: Vec<Value>,
+}
+
+pub fn load_file(path: &str) -> Result<Vec<Value>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let file: PolicyFile =
+        serde_yaml::from_str(&contents).map_err(|e| format!("{}: {}", path, e))?;
+
+    let mut names = Vec::new();
+    for policy in &file.policies {
+        let name = policy_name(policy).ok_or_else(|| format!("{}: policy without a name", path))?;
+        if names.contains(&name) {
+            return Err(format!("{}: duplicate policy name {}", path, name));
+        }
+        names.push(name);
+    }
+
+    Ok(file.policies.iter().map(normalize).collect())
+}
+
+pub fn policy_name(policy: &Value) -> Option<&str> {
+    policy["name"].as_str()
+}
+
+pub fn is_active(policy: &Value) -> bool {
+    policy["status"].as_str() == Some("Active")
+}
+
+pub fn matches_wallet(policy: &Value, wallet_id: &str, tags: &[String]) -> bool {
+    let filters = &policy["filters"];
+    if let Some(wallet_ids) = filters["walletId"]["in"].as_array()
+        && !wallet_ids.iter().any(|id| id.as_str() == Some(wallet_id))
+    {
+        return false;
+    }
+    if let Some(wallet_tags) = filters["walletTags"]["hasAny"].as_array()
+        && !wallet_tags
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|tag| tags.iter().any(|wallet_tag| wallet_tag == tag))
+    {
+        return false;
+    }
+    if let Some(wallet_tags) = filters["walletTags"]["hasAll"].as_array()
+        && !wallet_tags
+            .iter()
+            .filter_map(Value::as_str)
+            .all(|tag| tags.iter().any(|wallet_tag| wallet_tag == tag))
+    {
+        return false;
+    }
+    true
+}
+
+pub fn normalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key.clone(), normalize(value)))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(normalize).collect()),
+        Value::Number(number) => match number.as_f64() {
+            Some(float) if float.fract() == 0.0 && float.abs() < 9e15 => Value::from(float as i64),
+            _ => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+pub fn managed_fields(policy: &Value) -> Value {
+    let mut fields = Map::new();
+    for field in MANAGED_FIELDS {
+        let value = normalize(&policy[*field]);
+        if !value.is_null() {
+            fields.insert(field.to_string(), value);
+        }
+    }
+    Value::Object(fields)
+}
+
+pub fn changed_fields(current: &Value, desired: &Value) -> Vec<(&'static str, Value, Value)> {
+    let current = managed_fields(current);
+    let desired = managed_fields(desired);
+    MANAGED_FIELDS
+        .iter()
+        .filter(|field| current[**field] != desired[**field])
+        .map(|field| (*field, current[*field].clone(), desired[*field].clone()))
+        .collect()
+}
+
+pub fn body<T: DeserializeOwned>(policy: &Value) -> Result<T, String> {
+    let mut body = managed_fields(policy);
+    body["name"] = policy["name"].clone();
+    serde_json::from_value(body).map_err(|e| {
+        format!(
+            "policy {}: {}",
+            policy_name(policy).unwrap_or("<unnamed>"),
+            e
+        )
+    })
+}
+
+pub async fn list_policies(client: &DfnsApiClient) -> Result<Vec<Value>, String> {
+    let mut policies = Vec::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListPoliciesRequest {
+            query: Some(ListPoliciesRequestQuery {
+                limit: Some("100".to_string()),
+                status: None,
+                pagination_token: pagination_token.take(),
+            }),
+        };
+
+        let response = client
+            .policies()
+            .list_policies(Some(request))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let page = serde_json::to_value(&response).map_err(|e| e.to_string())?;
+        policies.extend(page["items"].as_array().into_iter().flatten().cloned());
+
+        match page["nextPageToken"].as_str() {
+            Some(token) => pagination_token = Some(token.to_string()),
+            None => break,
+        }
+    }
+
+    Ok(policies)
+}
+
+pub async fn active_policies(client: &DfnsApiClient) -> Result<Vec<Value>, String> {
+    Ok(list_policies(client)
+        .await?
+        .into_iter()
+        .filter(is_active)
+        .collect())
+}
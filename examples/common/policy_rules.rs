@@ -0,0 +1,503 @@
+#![allow(dead_code)]
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    Usd,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum AlertLevel {
+    Low,
+    Medium,
+    High,
+    Severe,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", content = "configuration")]
+pub enum Rule {
+    AlwaysTrigger,
+    TransactionAmountLimit(AmountLimit),
+    TransactionAmountVelocity(AmountVelocity),
+    TransactionCountVelocity(CountVelocity),
+    TransactionRecipientWhitelist(RecipientWhitelist),
+    ChainalysisTransactionPrescreening(ChainalysisPrescreening),
+    ChainalysisTransactionScreening(ChainalysisScreening),
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AmountLimit {
+    pub limit: f64,
+    pub currency: Currency,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AmountVelocity {
+    pub limit: f64,
+    pub currency: Currency,
+    pub timeframe: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CountVelocity {
+    pub limit: u64,
+    pub timeframe: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RecipientWhitelist {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alerts {
+    pub alert_level: AlertLevel,
+    pub category_ids: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Categories {
+    pub category_ids: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Exposures {
+    pub direct: Categories,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FallbackBehaviours {
+    pub skip_unscreenable_transaction: bool,
+    pub skip_unsupported_network: bool,
+    pub skip_unsupported_asset: bool,
+    pub skip_chainalysis_failure: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainalysisPrescreening {
+    pub alerts: Alerts,
+    pub exposures: Exposures,
+    pub addresses: Categories,
+    pub fallback_behaviours: FallbackBehaviours,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainalysisScreening {
+    pub alerts: Alerts,
+    pub exposures: Exposures,
+    pub fallback_behaviours: FallbackBehaviours,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind")]
+pub enum Action {
+    RequestApproval(ApprovalAction),
+    Block,
+    #[serde(rename = "NoAction")]
+    None,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalAction {
+    pub approval_groups: Vec<ApprovalGroup>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_reject_timeout: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InList {
+    #[serde(rename = "in")]
+    pub values: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Approvers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<InList>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub quorum: u32,
+    pub approvers: Approvers,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TagFilter {
+    #[serde(rename = "hasAny")]
+    pub has_any: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Filters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_id: Option<InList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_tags: Option<TagFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<InList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_id: Option<InList>,
+}
+
+fn minutes(timeframe: Duration) -> Result<u64, String> {
+    let seconds = timeframe.as_secs();
+    if seconds == 0 || !seconds.is_multiple_of(60) || timeframe.subsec_nanos() != 0 {
+        return Err(format!(
+            "timeframe must be a whole number of minutes, got {}s",
+            seconds
+        ));
+    }
+    Ok(seconds / 60)
+}
+
+fn convert<T: DeserializeOwned>(value: Value) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+impl Rule {
+    pub fn amount_limit(limit: f64, currency: Currency) -> Self {
+        Rule::TransactionAmountLimit(AmountLimit { limit, currency })
+    }
+
+    pub fn amount_velocity(
+        limit: f64,
+        currency: Currency,
+        timeframe: Duration,
+    ) -> Result<Self, String> {
+        Ok(Rule::TransactionAmountVelocity(AmountVelocity {
+            limit,
+            currency,
+            timeframe: minutes(timeframe)?,
+        }))
+    }
+
+    pub fn count_velocity(limit: u64, timeframe: Duration) -> Result<Self, String> {
+        Ok(Rule::TransactionCountVelocity(CountVelocity {
+            limit,
+            timeframe: minutes(timeframe)?,
+        }))
+    }
+
+    pub fn recipient_whitelist<I, S>(addresses: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Rule::TransactionRecipientWhitelist(RecipientWhitelist {
+            addresses: addresses.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Rule::AlwaysTrigger => "AlwaysTrigger",
+            Rule::TransactionAmountLimit(_) => "TransactionAmountLimit",
+            Rule::TransactionAmountVelocity(_) => "TransactionAmountVelocity",
+            Rule::TransactionCountVelocity(_) => "TransactionCountVelocity",
+            Rule::TransactionRecipientWhitelist(_) => "TransactionRecipientWhitelist",
+            Rule::ChainalysisTransactionPrescreening(_) => "ChainalysisTransactionPrescreening",
+            Rule::ChainalysisTransactionScreening(_) => "ChainalysisTransactionScreening",
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Rule::TransactionAmountLimit(AmountLimit { limit, .. })
+            | Rule::TransactionAmountVelocity(AmountVelocity { limit, .. })
+                if !(limit.is_finite() && *limit > 0.0) =>
+            {
+                Err(format!("{} limit must be positive", self.kind()))
+            }
+            Rule::TransactionCountVelocity(CountVelocity { limit: 0, .. }) => {
+                Err("TransactionCountVelocity limit must be positive".to_string())
+            }
+            Rule::TransactionRecipientWhitelist(whitelist) if whitelist.addresses.is_empty() => {
+                Err("TransactionRecipientWhitelist needs at least one address".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<Value, String> {
+        self.validate()?;
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    pub fn to_body<T: DeserializeOwned>(&self) -> Result<T, String> {
+        convert(self.to_json()?)
+    }
+}
+
+impl RecipientWhitelist {
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.addresses.push(address.into());
+        self
+    }
+
+    pub fn build(self) -> Rule {
+        Rule::TransactionRecipientWhitelist(self)
+    }
+}
+
+impl Alerts {
+    pub fn new(alert_level: AlertLevel) -> Self {
+        Self {
+            alert_level,
+            category_ids: Vec::new(),
+        }
+    }
+
+    pub fn categories(mut self, category_ids: impl IntoIterator<Item = u32>) -> Self {
+        self.category_ids.extend(category_ids);
+        self
+    }
+}
+
+impl Categories {
+    pub fn new(category_ids: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            category_ids: category_ids.into_iter().collect(),
+        }
+    }
+}
+
+impl FallbackBehaviours {
+    pub fn skip_all() -> Self {
+        Self {
+            skip_unscreenable_transaction: true,
+            skip_unsupported_network: true,
+            skip_unsupported_asset: true,
+            skip_chainalysis_failure: true,
+        }
+    }
+}
+
+impl ChainalysisPrescreening {
+    pub fn new(alerts: Alerts, fallback_behaviours: FallbackBehaviours) -> Self {
+        Self {
+            alerts,
+            exposures: Exposures::default(),
+            addresses: Categories::default(),
+            fallback_behaviours,
+        }
+    }
+
+    pub fn direct_exposures(mut self, exposures: Categories) -> Self {
+        self.exposures.direct = exposures;
+        self
+    }
+
+    pub fn addresses(mut self, addresses: Categories) -> Self {
+        self.addresses = addresses;
+        self
+    }
+
+    pub fn build(self) -> Rule {
+        Rule::ChainalysisTransactionPrescreening(self)
+    }
+}
+
+impl ChainalysisScreening {
+    pub fn new(alerts: Alerts, fallback_behaviours: FallbackBehaviours) -> Self {
+        Self {
+            alerts,
+            exposures: Exposures::default(),
+            fallback_behaviours,
+        }
+    }
+
+    pub fn direct_exposures(mut self, exposures: Categories) -> Self {
+        self.exposures.direct = exposures;
+        self
+    }
+
+    pub fn build(self) -> Rule {
+        Rule::ChainalysisTransactionScreening(self)
+    }
+}
+
+impl ApprovalGroup {
+    pub fn new(quorum: u32) -> Self {
+        Self {
+            name: None,
+            quorum,
+            approvers: Approvers::default(),
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn approver(mut self, user_id: impl Into<String>) -> Self {
+        self.approvers
+            .user_id
+            .get_or_insert_with(|| InList { values: Vec::new() })
+            .values
+            .push(user_id.into());
+        self
+    }
+
+    pub fn approvers<I, S>(self, user_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        user_ids.into_iter().fold(self, Self::approver)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let label = self.name.as_deref().unwrap_or("approval group");
+        if self.quorum == 0 {
+            return Err(format!("{}: quorum must be at least 1", label));
+        }
+        if let Some(approvers) = &self.approvers.user_id {
+            let mut unique = approvers.values.clone();
+            unique.sort();
+            unique.dedup();
+            if unique.len() != approvers.values.len() {
+                return Err(format!("{}: duplicate approvers", label));
+            }
+            if (self.quorum as usize) > unique.len() {
+                return Err(format!(
+                    "{}: quorum {} exceeds {} approvers",
+                    label,
+                    self.quorum,
+                    unique.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Action {
+    pub fn request_approval() -> Self {
+        Action::RequestApproval(ApprovalAction::default())
+    }
+
+    pub fn group(mut self, group: ApprovalGroup) -> Self {
+        if let Action::RequestApproval(action) = &mut self {
+            action.approval_groups.push(group);
+        }
+        self
+    }
+
+    pub fn auto_reject_timeout_minutes(mut self, minutes: u64) -> Self {
+        if let Action::RequestApproval(action) = &mut self {
+            action.auto_reject_timeout = Some(minutes);
+        }
+        self
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Action::RequestApproval(_) => "RequestApproval",
+            Action::Block => "Block",
+            Action::None => "NoAction",
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let Action::RequestApproval(action) = self else {
+            return Ok(());
+        };
+        if action.approval_groups.is_empty() {
+            return Err("RequestApproval needs at least one approval group".to_string());
+        }
+        action
+            .approval_groups
+            .iter()
+            .try_for_each(ApprovalGroup::validate)
+    }
+
+    pub fn to_json(&self) -> Result<Value, String> {
+        self.validate()?;
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    pub fn to_body<T: DeserializeOwned>(&self) -> Result<T, String> {
+        convert(self.to_json()?)
+    }
+}
+
+impl Filters {
+    pub fn wallet_ids<I, S>(wallet_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            wallet_id: Some(InList {
+                values: wallet_ids.into_iter().map(Into::into).collect(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn wallet_tags<I, S>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            wallet_tags: Some(TagFilter {
+                has_any: tags.into_iter().map(Into::into).collect(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn policy_ids<I, S>(policy_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            policy_id: Some(InList {
+                values: policy_ids.into_iter().map(Into::into).collect(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn permission_ids<I, S>(permission_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            permission_id: Some(InList {
+                values: permission_ids.into_iter().map(Into::into).collect(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn to_json(&self) -> Result<Value, String> {
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    pub fn to_body<T: DeserializeOwned>(&self) -> Result<T, String> {
+        convert(self.to_json()?)
+    }
+}
@@ -0,0 +1,570 @@
+#![allow(dead_code)]
+
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+const VERSION_PREFIX: u8 = 0x80;
+const SIGNATURE_LEN: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    pub fn from_base58(encoded: &str) -> Result<Self, String> {
+        let bytes = bs58::decode(encoded)
+            .into_vec()
+            .map_err(|e| format!("invalid base58 public key {}: {}", encoded, e))?;
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| format!("public key {} is not 32 bytes", encoded))?;
+        Ok(Self(bytes))
+    }
+
+    pub fn to_base58(self) -> String {
+        bs58::encode(self.0).into_string()
+    }
+
+    fn is_on_curve(&self) -> bool {
+        CompressedEdwardsY(self.0).decompress().is_some()
+    }
+
+    pub fn find_program_address(
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<(Self, u8), String> {
+        for bump in (0..=u8::MAX).rev() {
+            let mut hasher = Sha256::new();
+            for seed in seeds {
+                hasher.update(seed);
+            }
+            hasher.update([bump]);
+            hasher.update(program_id.0);
+            hasher.update(PDA_MARKER);
+
+            let candidate = Pubkey(hasher.finalize().into());
+            if !candidate.is_on_curve() {
+                return Ok((candidate, bump));
+            }
+        }
+
+        Err("unable to find a valid program address".to_string())
+    }
+}
+
+impl fmt::Debug for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_base58())
+    }
+}
+
+impl fmt::Display for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_base58())
+    }
+}
+
+fn program_id(encoded: &str) -> Pubkey {
+    Pubkey::from_base58(encoded).expect("well-known program id")
+}
+
+#[derive(Clone, Debug)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    pub fn writable(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    pub fn readonly(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+pub fn system_transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: program_id(SYSTEM_PROGRAM_ID),
+        accounts: vec![
+            AccountMeta::writable(*from, true),
+            AccountMeta::writable(*to, false),
+        ],
+        data,
+    }
+}
+
+pub fn associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<Pubkey, String> {
+    let (address, _) = Pubkey::find_program_address(
+        &[&owner.0, &token_program.0, &mint.0],
+        &program_id(ASSOCIATED_TOKEN_PROGRAM_ID),
+    )?;
+    Ok(address)
+}
+
+pub fn create_associated_token_account_idempotent(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<Instruction, String> {
+    let associated_account = associated_token_address(owner, mint, token_program)?;
+
+    Ok(Instruction {
+        program_id: program_id(ASSOCIATED_TOKEN_PROGRAM_ID),
+        accounts: vec![
+            AccountMeta::writable(*payer, true),
+            AccountMeta::writable(associated_account, false),
+            AccountMeta::readonly(*owner, false),
+            AccountMeta::readonly(*mint, false),
+            AccountMeta::readonly(program_id(SYSTEM_PROGRAM_ID), false),
+            AccountMeta::readonly(*token_program, false),
+        ],
+        data: vec![1],
+    })
+}
+
+pub fn spl_transfer_checked(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    token_program: &Pubkey,
+) -> Instruction {
+    let mut data = vec![12];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::writable(*source, false),
+            AccountMeta::readonly(*mint, false),
+            AccountMeta::writable(*destination, false),
+            AccountMeta::readonly(*owner, true),
+        ],
+        data,
+    }
+}
+
+pub fn spl_transfer_with_ata(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    token_program: &Pubkey,
+) -> Result<Vec<Instruction>, String> {
+    let source = associated_token_address(owner, mint, token_program)?;
+    let destination = associated_token_address(recipient, mint, token_program)?;
+
+    Ok(vec![
+        create_associated_token_account_idempotent(payer, recipient, mint, token_program)?,
+        spl_transfer_checked(
+            &source,
+            mint,
+            &destination,
+            owner,
+            amount,
+            decimals,
+            token_program,
+        ),
+    ])
+}
+
+pub struct AddressLookupTable {
+    pub key: Pubkey,
+    pub addresses: Vec<Pubkey>,
+}
+
+struct LookupEntry {
+    key: Pubkey,
+    writable_indexes: Vec<u8>,
+    readonly_indexes: Vec<u8>,
+}
+
+struct CompiledInstruction {
+    program_id_index: u8,
+    accounts: Vec<u8>,
+    data: Vec<u8>,
+}
+
+pub enum MessageVersion {
+    Legacy,
+    V0,
+}
+
+pub struct Message {
+    version: MessageVersion,
+    num_required_signatures: u8,
+    num_readonly_signed: u8,
+    num_readonly_unsigned: u8,
+    account_keys: Vec<Pubkey>,
+    recent_blockhash: [u8; 32],
+    instructions: Vec<CompiledInstruction>,
+    lookups: Vec<LookupEntry>,
+}
+
+#[derive(Clone)]
+struct KeyFlags {
+    pubkey: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    is_invoked: bool,
+}
+
+impl Message {
+    pub fn legacy(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: &str,
+    ) -> Result<Self, String> {
+        Self::compile(
+            MessageVersion::Legacy,
+            payer,
+            instructions,
+            recent_blockhash,
+            &[],
+        )
+    }
+
+    pub fn v0(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: &str,
+        lookup_tables: &[AddressLookupTable],
+    ) -> Result<Self, String> {
+        Self::compile(
+            MessageVersion::V0,
+            payer,
+            instructions,
+            recent_blockhash,
+            lookup_tables,
+        )
+    }
+
+    fn compile(
+        version: MessageVersion,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: &str,
+        lookup_tables: &[AddressLookupTable],
+    ) -> Result<Self, String> {
+        let recent_blockhash = Pubkey::from_base58(recent_blockhash)?.0;
+
+        let mut keys: Vec<KeyFlags> = vec![KeyFlags {
+            pubkey: *payer,
+            is_signer: true,
+            is_writable: true,
+            is_invoked: false,
+        }];
+        let mut upsert =
+            |pubkey: Pubkey, is_signer: bool, is_writable: bool, is_invoked: bool| match keys
+                .iter_mut()
+                .find(|key| key.pubkey == pubkey)
+            {
+                Some(key) => {
+                    key.is_signer |= is_signer;
+                    key.is_writable |= is_writable;
+                    key.is_invoked |= is_invoked;
+                }
+                None => keys.push(KeyFlags {
+                    pubkey,
+                    is_signer,
+                    is_writable,
+                    is_invoked,
+                }),
+            };
+
+        for instruction in instructions {
+            upsert(instruction.program_id, false, false, true);
+            for account in &instruction.accounts {
+                upsert(
+                    account.pubkey,
+                    account.is_signer,
+                    account.is_writable,
+                    false,
+                );
+            }
+        }
+
+        let mut lookups = Vec::new();
+        let mut loaded_writable = Vec::new();
+        let mut loaded_readonly = Vec::new();
+        for table in lookup_tables {
+            let mut entry = LookupEntry {
+                key: table.key,
+                writable_indexes: Vec::new(),
+                readonly_indexes: Vec::new(),
+            };
+
+            keys.retain(|key| {
+                if key.is_signer || key.is_invoked {
+                    return true;
+                }
+                let Some(index) = table.addresses.iter().position(|a| *a == key.pubkey) else {
+                    return true;
+                };
+                if key.is_writable {
+                    entry.writable_indexes.push(index as u8);
+                    loaded_writable.push(key.pubkey);
+                } else {
+                    entry.readonly_indexes.push(index as u8);
+                    loaded_readonly.push(key.pubkey);
+                }
+                false
+            });
+
+            if !entry.writable_indexes.is_empty() || !entry.readonly_indexes.is_empty() {
+                lookups.push(entry);
+            }
+        }
+
+        let (payer_key, rest) = keys.split_first().expect("payer is always present");
+        let mut ordered = vec![payer_key.clone()];
+        for (is_signer, is_writable) in [(true, true), (true, false), (false, true), (false, false)]
+        {
+            ordered.extend(
+                rest.iter()
+                    .filter(|key| key.is_signer == is_signer && key.is_writable == is_writable)
+                    .cloned(),
+            );
+        }
+
+        let account_keys: Vec<Pubkey> = ordered.iter().map(|key| key.pubkey).collect();
+        let all_keys: Vec<Pubkey> = account_keys
+            .iter()
+            .chain(&loaded_writable)
+            .chain(&loaded_readonly)
+            .copied()
+            .collect();
+        if all_keys.len() > 256 {
+            return Err("too many accounts referenced by the message".to_string());
+        }
+
+        let index_of = |pubkey: &Pubkey| -> u8 {
+            all_keys
+                .iter()
+                .position(|key| key == pubkey)
+                .expect("every referenced account is indexed") as u8
+        };
+
+        let compiled = instructions
+            .iter()
+            .map(|instruction| CompiledInstruction {
+                program_id_index: index_of(&instruction.program_id),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|account| index_of(&account.pubkey))
+                    .collect(),
+                data: instruction.data.clone(),
+            })
+            .collect();
+
+        let count = |is_signer: bool, is_writable: bool| {
+            ordered
+                .iter()
+                .filter(|key| key.is_signer == is_signer && key.is_writable == is_writable)
+                .count() as u8
+        };
+
+        Ok(Self {
+            version,
+            num_required_signatures: count(true, true) + count(true, false),
+            num_readonly_signed: count(true, false),
+            num_readonly_unsigned: count(false, false),
+            account_keys,
+            recent_blockhash,
+            instructions: compiled,
+            lookups,
+        })
+    }
+
+    pub fn num_required_signatures(&self) -> usize {
+        self.num_required_signatures as usize
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        if let MessageVersion::V0 = self.version {
+            out.push(VERSION_PREFIX);
+        }
+
+        out.extend_from_slice(&[
+            self.num_required_signatures,
+            self.num_readonly_signed,
+            self.num_readonly_unsigned,
+        ]);
+
+        write_compact_u16(&mut out, self.account_keys.len())?;
+        for key in &self.account_keys {
+            out.extend_from_slice(&key.0);
+        }
+        out.extend_from_slice(&self.recent_blockhash);
+
+        write_compact_u16(&mut out, self.instructions.len())?;
+        for instruction in &self.instructions {
+            out.push(instruction.program_id_index);
+            write_compact_u16(&mut out, instruction.accounts.len())?;
+            out.extend_from_slice(&instruction.accounts);
+            write_compact_u16(&mut out, instruction.data.len())?;
+            out.extend_from_slice(&instruction.data);
+        }
+
+        if let MessageVersion::V0 = self.version {
+            write_compact_u16(&mut out, self.lookups.len())?;
+            for lookup in &self.lookups {
+                out.extend_from_slice(&lookup.key.0);
+                write_compact_u16(&mut out, lookup.writable_indexes.len())?;
+                out.extend_from_slice(&lookup.writable_indexes);
+                write_compact_u16(&mut out, lookup.readonly_indexes.len())?;
+                out.extend_from_slice(&lookup.readonly_indexes);
+            }
+        }
+
+        Ok(out)
+    }
+
+    pub fn to_unsigned_transaction_hex(&self) -> Result<String, String> {
+        let mut out = Vec::new();
+        write_compact_u16(&mut out, self.num_required_signatures())?;
+        out.resize(
+            out.len() + SIGNATURE_LEN * self.num_required_signatures(),
+            0,
+        );
+        out.extend_from_slice(&self.serialize()?);
+        Ok(format!("0x{}", hex::encode(out)))
+    }
+}
+
+pub struct SignedTransaction {
+    pub signatures: Vec<[u8; SIGNATURE_LEN]>,
+    pub message: Vec<u8>,
+}
+
+impl SignedTransaction {
+    pub fn decode(encoded: &str) -> Result<Self, String> {
+        let bytes = decode_bytes(encoded)?;
+        let (count, mut offset) = read_compact_u16(&bytes)?;
+        if count * SIGNATURE_LEN > bytes.len() - offset {
+            return Err("transaction is truncated".to_string());
+        }
+
+        let mut signatures = Vec::with_capacity(count);
+        for _ in 0..count {
+            let signature = bytes
+                .get(offset..offset + SIGNATURE_LEN)
+                .ok_or_else(|| "transaction is truncated".to_string())?;
+            signatures.push(signature.try_into().unwrap());
+            offset += SIGNATURE_LEN;
+        }
+
+        Ok(Self {
+            signatures,
+            message: bytes[offset..].to_vec(),
+        })
+    }
+
+    pub fn is_fully_signed(&self) -> bool {
+        self.signatures
+            .iter()
+            .all(|signature| signature.iter().any(|byte| *byte != 0))
+    }
+
+    pub fn transaction_id(&self) -> Option<String> {
+        self.signatures
+            .first()
+            .map(|signature| bs58::encode(signature).into_string())
+    }
+
+    pub fn to_base64(&self) -> Result<String, String> {
+        use base64::Engine;
+
+        let mut out = Vec::new();
+        write_compact_u16(&mut out, self.signatures.len())?;
+        for signature in &self.signatures {
+            out.extend_from_slice(signature);
+        }
+        out.extend_from_slice(&self.message);
+        Ok(base64::engine::general_purpose::STANDARD.encode(out))
+    }
+}
+
+fn decode_bytes(encoded: &str) -> Result<Vec<u8>, String> {
+    let encoded = encoded.trim();
+    if let Some(stripped) = encoded.strip_prefix("0x") {
+        return hex::decode(stripped).map_err(|e| e.to_string());
+    }
+    hex::decode(encoded).or_else(|_| {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| e.to_string())
+    })
+}
+
+fn write_compact_u16(out: &mut Vec<u8>, value: usize) -> Result<(), String> {
+    let mut value = u16::try_from(value)
+        .map_err(|_| format!("length {} does not fit in a compact-u16", value))?;
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return Ok(());
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
+
+fn read_compact_u16(bytes: &[u8]) -> Result<(usize, usize), String> {
+    let mut value = 0usize;
+    for (position, byte) in bytes.iter().take(3).enumerate() {
+        if position == 2 && *byte > 0x03 {
+            return Err("compact-u16 length exceeds u16::MAX".to_string());
+        }
+        value |= ((byte & 0x7f) as usize) << (7 * position);
+        if byte & 0x80 == 0 {
+            if position > 0 && *byte == 0 {
+                return Err("non-canonical compact-u16 length".to_string());
+            }
+            return Ok((value, position + 1));
+        }
+    }
+    Err("invalid compact-u16 length".to_string())
+}
@@ -0,0 +1,342 @@
+#![allow(dead_code)]
+
+use crate::{
+    abi::{self, Function, Token},
+    evm,
+    multicall::{self, Batch, Call, CallResult},
+};
+use dfns_sdk_rs::{DfnsApiClient, api::networks::types::ReadContractBodyNetwork};
+use ruint::aliases::U256;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+impl TokenMetadata {
+    pub fn is_complete(&self) -> bool {
+        self.name.is_some() && self.symbol.is_some() && self.decimals.is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.symbol.is_none() && self.decimals.is_none()
+    }
+
+    pub fn format_units(&self, amount: U256) -> Result<String, String> {
+        let decimals = self
+            .decimals
+            .ok_or_else(|| format!("decimals unknown, raw amount {}", amount))?;
+        let value = evm::format_units(amount, decimals);
+        Ok(match &self.symbol {
+            Some(symbol) => format!("{} {}", value, symbol),
+            None => value,
+        })
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct MetadataCache {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    tokens: BTreeMap<String, TokenMetadata>,
+    #[serde(skip)]
+    partial: BTreeMap<String, TokenMetadata>,
+}
+
+impl MetadataCache {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let mut cache = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("failed to parse {}: {}", path.display(), e))?,
+            Err(_) => MetadataCache::default(),
+        };
+        cache.tokens.retain(|_, metadata| metadata.is_complete());
+        cache.path = Some(path);
+        Ok(cache)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn get(&self, network: &ReadContractBodyNetwork, token: &str) -> Option<&TokenMetadata> {
+        let key = Self::key(network, token);
+        self.tokens.get(&key).or_else(|| self.partial.get(&key))
+    }
+
+    pub fn insert(
+        &mut self,
+        network: &ReadContractBodyNetwork,
+        token: &str,
+        metadata: TokenMetadata,
+    ) {
+        let key = Self::key(network, token);
+        if metadata.is_complete() {
+            self.partial.remove(&key);
+            self.tokens.insert(key, metadata);
+        } else {
+            self.partial.insert(key, metadata);
+        }
+    }
+
+    fn key(network: &ReadContractBodyNetwork, token: &str) -> String {
+        format!("{:?}:{}", network, token.to_lowercase())
+    }
+}
+
+pub struct TokenClient<'a> {
+    client: &'a DfnsApiClient,
+    network: ReadContractBodyNetwork,
+    cache: MetadataCache,
+    chunk_size: usize,
+}
+
+fn decode_text(data: &[u8]) -> Option<String> {
+    if let Ok(Some(value)) = abi::decode(&[abi::ParamType::String], data)
+        .map(|tokens| tokens.into_iter().next().and_then(Token::into_string))
+    {
+        return Some(value);
+    }
+
+    if data.len() == 32 {
+        let end = data.iter().position(|b| *b == 0).unwrap_or(32);
+        return Some(String::from_utf8_lossy(&data[..end]).to_string());
+    }
+
+    None
+}
+
+fn decode_decimals(result: &CallResult) -> Option<u8> {
+    if !result.success {
+        return None;
+    }
+    abi::decode(&[abi::ParamType::Uint(8)], &result.data)
+        .ok()?
+        .into_iter()
+        .next()
+        .and_then(Token::into_uint)
+        .and_then(|decimals| u8::try_from(decimals).ok())
+}
+
+impl<'a> TokenClient<'a> {
+    pub fn new(
+        client: &'a DfnsApiClient,
+        network: ReadContractBodyNetwork,
+        cache: MetadataCache,
+    ) -> Self {
+        Self {
+            client,
+            network,
+            cache,
+            chunk_size: multicall::DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    pub fn network(&self) -> &ReadContractBodyNetwork {
+        &self.network
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    pub fn cache(&self) -> &MetadataCache {
+        &self.cache
+    }
+
+    pub fn into_cache(self) -> MetadataCache {
+        self.cache
+    }
+
+    pub async fn metadata(&mut self, token: &str) -> Result<TokenMetadata, String> {
+        if let Some(metadata) = self.cache.get(&self.network, token) {
+            return Ok(metadata.clone());
+        }
+
+        let metadata = TokenMetadata {
+            name: self.text(token, "name()").await.ok(),
+            symbol: self.text(token, "symbol()").await.ok(),
+            decimals: self
+                .uint(token, "decimals()(uint8)", &[])
+                .await
+                .ok()
+                .and_then(|decimals| u8::try_from(decimals).ok()),
+        };
+        if metadata.is_empty() {
+            return Err(format!("{} does not expose token metadata", token));
+        }
+
+        self.cache.insert(&self.network, token, metadata.clone());
+        Ok(metadata)
+    }
+
+    pub async fn prefetch_metadata(&mut self, tokens: &[String]) -> Result<(), String> {
+        let missing: Vec<&String> = tokens
+            .iter()
+            .filter(|token| self.cache.get(&self.network, token).is_none())
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let selectors = ["name()", "symbol()", "decimals()"]
+            .iter()
+            .map(|signature| Function::parse(signature).map(|function| function.selector()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut calls = Vec::with_capacity(missing.len() * selectors.len());
+        for token in &missing {
+            for selector in &selectors {
+                calls.push(Call {
+                    target: token.to_string(),
+                    data: selector.to_vec(),
+                    allow_failure: true,
+                });
+            }
+        }
+
+        let results =
+            multicall::aggregate3(self.client, &self.network, &calls, self.chunk_size).await?;
+        let text = |result: &CallResult| match result.success {
+            true => decode_text(&result.data),
+            false => None,
+        };
+        for (token, results) in missing.into_iter().zip(results.chunks(selectors.len())) {
+            let metadata = TokenMetadata {
+                name: text(&results[0]),
+                symbol: text(&results[1]),
+                decimals: decode_decimals(&results[2]),
+            };
+            if !metadata.is_empty() {
+                self.cache.insert(&self.network, token, metadata);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn total_supply(&self, token: &str) -> Result<U256, String> {
+        self.uint(token, "totalSupply()(uint256)", &[]).await
+    }
+
+    pub async fn balance_of(&self, token: &str, owner: &str) -> Result<U256, String> {
+        let owner = Token::Address(abi::parse_address(owner)?);
+        self.uint(token, "balanceOf(address)(uint256)", &[owner])
+            .await
+    }
+
+    pub async fn balances_of(
+        &self,
+        tokens: &[String],
+        owner: &str,
+    ) -> Result<Vec<Result<U256, String>>, String> {
+        let function = Function::parse("balanceOf(address)(uint256)")?;
+        let owner = Token::Address(abi::parse_address(owner)?);
+        let mut batch = Batch::with_chunk_size(self.chunk_size);
+        for token in tokens {
+            batch.add(token, &function, std::slice::from_ref(&owner))?;
+        }
+
+        Ok(batch
+            .execute(self.client, &self.network)
+            .await?
+            .into_iter()
+            .map(|result| {
+                result?
+                    .into_iter()
+                    .next()
+                    .and_then(Token::into_uint)
+                    .ok_or_else(|| "balanceOf returned no value".to_string())
+            })
+            .collect())
+    }
+
+    pub async fn allowance(&self, token: &str, owner: &str, spender: &str) -> Result<U256, String> {
+        let args = [
+            Token::Address(abi::parse_address(owner)?),
+            Token::Address(abi::parse_address(spender)?),
+        ];
+        self.uint(token, "allowance(address,address)(uint256)", &args)
+            .await
+    }
+
+    pub async fn owner_of(&self, token: &str, token_id: U256) -> Result<String, String> {
+        let function = Function::parse("ownerOf(uint256)(address)")?;
+        let outputs = evm::call(
+            self.client,
+            &self.network,
+            token,
+            &function,
+            &[Token::Uint(token_id)],
+        )
+        .await?;
+        outputs
+            .into_iter()
+            .next()
+            .and_then(Token::into_address)
+            .map(|address| abi::checksum_address(&address))
+            .ok_or_else(|| "ownerOf returned no address".to_string())
+    }
+
+    pub async fn token_uri(&self, token: &str, token_id: U256) -> Result<String, String> {
+        let function = Function::parse("tokenURI(uint256)(string)")?;
+        let outputs = evm::call(
+            self.client,
+            &self.network,
+            token,
+            &function,
+            &[Token::Uint(token_id)],
+        )
+        .await?;
+        outputs
+            .into_iter()
+            .next()
+            .and_then(Token::into_string)
+            .ok_or_else(|| "tokenURI returned no string".to_string())
+    }
+
+    pub async fn format_amount(&mut self, token: &str, amount: U256) -> Result<String, String> {
+        self.metadata(token)
+            .await?
+            .format_units(amount)
+            .map_err(|e| format!("cannot format {} amount: {}", token, e))
+    }
+
+    async fn uint(&self, token: &str, signature: &str, args: &[Token]) -> Result<U256, String> {
+        let function = Function::parse(signature)?;
+        evm::call(self.client, &self.network, token, &function, args)
+            .await?
+            .into_iter()
+            .next()
+            .and_then(Token::into_uint)
+            .ok_or_else(|| format!("{} returned no value", function.signature()))
+    }
+
+    async fn text(&self, token: &str, signature: &str) -> Result<String, String> {
+        let function = Function::parse(signature)?;
+        let data = evm::call_raw(self.client, &self.network, token, &function.selector()).await?;
+        decode_text(&data)
+            .ok_or_else(|| format!("{} returned undecodable data", function.signature()))
+    }
+}
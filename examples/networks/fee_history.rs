@@ -0,0 +1,298 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/fee_history.rs"]
+mod fee_history;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use fee_history::FeeStore;
+use futures::future;
+use std::{env, sync::Arc, time::Duration};
+
+const DEFAULT_STORE: &str = "fee_history.jsonl";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const DEFAULT_LOOKBACK: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+const DEFAULT_PERCENTILE: u8 = 75;
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    command: String,
+    networks: Vec<String>,
+    store: String,
+    interval: Duration,
+    samples: Option<usize>,
+    retention: Duration,
+    lookback: Duration,
+    window: Duration,
+    percentile: u8,
+    block_time: Option<f64>,
+}
+
+fn parse_secs(value: &str, flag: &str) -> Result<Duration, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+        .ok_or_else(|| {
+            format!(
+                "invalid {}: {} (expected a positive number of seconds)",
+                flag, value
+            )
+        })
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            command: args.next().unwrap_or_else(|| "recommend".to_string()),
+            networks: Vec::new(),
+            store: DEFAULT_STORE.to_string(),
+            interval: DEFAULT_INTERVAL,
+            samples: None,
+            retention: DEFAULT_RETENTION,
+            lookback: DEFAULT_LOOKBACK,
+            window: DEFAULT_WINDOW,
+            percentile: DEFAULT_PERCENTILE,
+            block_time: None,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--network" => options.networks.push(value()?),
+                "--store" => options.store = value()?,
+                "--interval" => options.interval = parse_secs(&value()?, &arg)?,
+                "--samples" => {
+                    options.samples = Some(value()?.parse().map_err(|_| "invalid --samples")?)
+                }
+                "--retention" => options.retention = parse_secs(&value()?, &arg)?,
+                "--lookback" => options.lookback = parse_secs(&value()?, &arg)?,
+                "--window" => options.window = parse_secs(&value()?, &arg)?,
+                "--percentile" => {
+                    options.percentile = value()?.parse().map_err(|_| "invalid --percentile")?
+                }
+                "--block-time" => {
+                    let block_time = value()?;
+                    options.block_time = Some(
+                        block_time
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|secs| secs.is_finite() && *secs > 0.0)
+                            .ok_or_else(|| format!("invalid --block-time: {}", block_time))?,
+                    )
+                }
+                other => return Err(format!("unknown argument {}", other)),
+            }
+        }
+
+        if options.networks.is_empty() {
+            options.networks.push("Ethereum".to_string());
+        }
+        Ok(options)
+    }
+}
+
+async fn record(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let networks = options
+        .networks
+        .iter()
+        .map(|name| fee_history::parse_network(name))
+        .collect::<Result<Vec<_>, _>>()?;
+    let store = FeeStore::new(&options.store);
+
+    let cutoff = fee_history::unix_now().saturating_sub(options.retention.as_secs());
+    let pruned = store.prune(cutoff)?;
+    if pruned > 0 {
+        println!("Pruned {} samples older than the retention window", pruned);
+    }
+
+    println!(
+        "Recording fees for {} every {}s into {}",
+        options.networks.join(", "),
+        options.interval.as_secs(),
+        options.store
+    );
+
+    let mut interval = tokio::time::interval(options.interval);
+    let mut recorded = 0;
+    while options.samples.is_none_or(|samples| recorded < samples) {
+        interval.tick().await;
+
+        let results = future::join_all(
+            networks
+                .iter()
+                .map(|network| fee_history::sample(client, network.clone())),
+        )
+        .await;
+
+        for (name, result) in options.networks.iter().zip(results) {
+            match result {
+                Ok(sample) => {
+                    store.append(&sample)?;
+                    let fees: Vec<String> = fee_history::TIERS
+                        .iter()
+                        .map(|tier| {
+                            let fee = sample.tier(tier).and_then(|tier| tier.fee());
+                            format!("{} {}", tier, fee.unwrap_or("-"))
+                        })
+                        .collect();
+                    println!(
+                        "[{}] {} block {}: {}",
+                        sample.timestamp,
+                        sample.network,
+                        sample
+                            .block_number
+                            .map(|block| block.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                        fees.join(", ")
+                    );
+                }
+                Err(e) => eprintln!("Failed to sample {}: {}", name, e),
+            }
+        }
+        recorded += 1;
+    }
+
+    Ok(())
+}
+
+fn recommend(options: &Options) -> Result<(), String> {
+    let store = FeeStore::new(&options.store);
+    let since = fee_history::unix_now().saturating_sub(options.lookback.as_secs());
+
+    for network in &options.networks {
+        let samples = store.load(network, since)?;
+        let recommendation = match fee_history::recommend(
+            &samples,
+            options.window,
+            options.percentile,
+            options.block_time,
+        ) {
+            Ok(recommendation) => recommendation,
+            Err(e) => {
+                eprintln!("{}: {}", network, e);
+                continue;
+            }
+        };
+
+        println!(
+            "{} (target {}s, {} samples over the last {}s):",
+            network,
+            options.window.as_secs(),
+            recommendation.samples,
+            options.lookback.as_secs()
+        );
+        match recommendation.block_time {
+            Some(block_time) => println!("  Observed block time: {:.1}s", block_time),
+            None => println!("  Observed block time: unknown (record longer or pass --block-time)"),
+        }
+        println!(
+            "  Priority: {:?} ({} tier)",
+            recommendation.priority, recommendation.tier
+        );
+        match recommendation.expected_wait {
+            Some(wait) if wait <= options.window => {
+                println!("  Expected confirmation: ~{}s", wait.as_secs())
+            }
+            Some(wait) => println!(
+                "  Expected confirmation: ~{}s, no tier meets the target window",
+                wait.as_secs()
+            ),
+            None => println!("  Expected confirmation: unknown"),
+        }
+        println!(
+            "  Current fee: {}  (median over lookback: {})",
+            recommendation.current_fee.as_deref().unwrap_or("-"),
+            recommendation.median_fee.as_deref().unwrap_or("-")
+        );
+        if let Some(fee) = &recommendation.fee {
+            println!("  Explicit fee (p{}): {}", options.percentile, fee);
+        }
+        if let Some(priority_fee) = &recommendation.priority_fee {
+            println!(
+                "  Explicit priority fee (p{}): {}",
+                options.percentile, priority_fee
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let result = match options.command.as_str() {
+        "record" => record(&client, &options).await,
+        "recommend" => recommend(&options),
+        other => Err(format!(
+            "unknown command {} (expected record or recommend)",
+            other
+        )),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+    }
+}
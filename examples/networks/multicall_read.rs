@@ -0,0 +1,187 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/multicall.rs"]
+mod multicall;
+
+use abi::{Function, Token};
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::networks::types::ReadContractBodyNetwork,
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use multicall::Batch;
+use serde::Deserialize;
+use std::{env, fs, sync::Arc};
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct CallSpec {
+    target: String,
+    function: String,
+    #[serde(default)]
+    args: Vec<serde_json::Value>,
+}
+
+struct Options {
+    calls_file: String,
+    network: ReadContractBodyNetwork,
+    batch_size: usize,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            calls_file: "calls.json".to_string(),
+            network: ReadContractBodyNetwork::Ethereum,
+            batch_size: multicall::DEFAULT_CHUNK_SIZE,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--batch-size" => {
+                    options.batch_size = value()?.parse().map_err(|_| "invalid --batch-size")?
+                }
+                "--network" => {
+                    let name = value()?;
+                    options.network =
+                        serde_json::from_value(serde_json::Value::String(name.clone()))
+                            .map_err(|_| format!("unsupported network: {}", name))?;
+                }
+                _ => options.calls_file = arg,
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn build_batch(specs: &[CallSpec], batch_size: usize) -> Result<Batch, String> {
+    let mut batch = Batch::with_chunk_size(batch_size);
+    for spec in specs {
+        let function = Function::parse(&spec.function)?;
+        if spec.args.len() != function.inputs.len() {
+            return Err(format!(
+                "{} expects {} arguments, got {}",
+                function.signature(),
+                function.inputs.len(),
+                spec.args.len()
+            ));
+        }
+        let args = function
+            .inputs
+            .iter()
+            .zip(&spec.args)
+            .map(|(param, value)| Token::from_json(param, value))
+            .collect::<Result<Vec<_>, _>>()?;
+        batch.add(&spec.target, &function, &args)?;
+    }
+    Ok(batch)
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let specs: Vec<CallSpec> = match fs::read_to_string(&options.calls_file)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+    {
+        Ok(specs) => specs,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", options.calls_file, e);
+            return;
+        }
+    };
+
+    let batch = match build_batch(&specs, options.batch_size) {
+        Ok(batch) => batch,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    println!(
+        "Executing {} calls through Multicall3 in batches of {}",
+        batch.len(),
+        options.batch_size
+    );
+
+    match batch.execute(&client, &options.network).await {
+        Ok(results) => {
+            let mut failed = 0;
+            for (spec, result) in specs.iter().zip(results) {
+                match result {
+                    Ok(tokens) => println!(
+                        "  {} {}: {}",
+                        spec.target,
+                        spec.function,
+                        serde_json::Value::Array(tokens.iter().map(Token::to_json).collect())
+                    ),
+                    Err(e) => {
+                        failed += 1;
+                        println!("  {} {}: failed, {}", spec.target, spec.function, e);
+                    }
+                }
+            }
+            println!("{} succeeded, {} failed", specs.len() - failed, failed);
+        }
+        Err(e) => eprintln!("Error: {}", e),
+    }
+}
@@ -0,0 +1,178 @@
+#[path = "../common/abi.rs"]
+mod abi;
+
+use abi::Function;
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::networks::types::{
+        Body, ReadContractBodyKind, ReadContractBodyNetwork, ReadContractRequest,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use std::{env, fs, sync::Arc};
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    contract: String,
+    function: Function,
+    args: Vec<String>,
+    network: ReadContractBodyNetwork,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut positional = Vec::new();
+        let mut abi_path = None;
+        let mut network = ReadContractBodyNetwork::Ethereum;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--abi" => abi_path = Some(value()?),
+                "--network" => {
+                    let name = value()?;
+                    network = serde_json::from_value(serde_json::Value::String(name.clone()))
+                        .map_err(|_| format!("unsupported network: {}", name))?;
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let contract = positional
+            .next()
+            .unwrap_or_else(|| "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string());
+        let function = positional
+            .next()
+            .unwrap_or_else(|| "balanceOf(address)(uint256)".to_string());
+        let mut args: Vec<String> = positional.collect();
+        if args.is_empty() && function == "balanceOf(address)(uint256)" {
+            args.push("0xe16c1623c1aa7d919cd2241d8b36d9e79c1be2a2".to_string());
+        }
+
+        let function = match abi_path {
+            Some(path) => {
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| format!("failed to read {}: {}", path, e))?;
+                let abi = serde_json::from_str(&content)
+                    .map_err(|e| format!("failed to parse {}: {}", path, e))?;
+                Function::from_json_abi(&abi, &function)?
+            }
+            None => Function::parse(&function)?,
+        };
+
+        Ok(Options {
+            contract,
+            function,
+            args,
+            network,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let calldata = match options.function.encode_args(&options.args) {
+        Ok(calldata) => format!("0x{}", hex::encode(calldata)),
+        Err(e) => {
+            eprintln!("Error encoding arguments: {}", e);
+            return;
+        }
+    };
+
+    println!("Function: {}", options.function.signature());
+    println!("Calldata: {}", calldata);
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let request = ReadContractRequest {
+        body: Body {
+            contract: options.contract,
+            data: calldata,
+            kind: ReadContractBodyKind::Evm,
+            network: options.network,
+        },
+    };
+
+    let response = match client.networks().read_contract(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            return;
+        }
+    };
+
+    println!("Raw data: {}", response.data);
+
+    if options.function.outputs.is_empty() {
+        println!("No output types declared, add them to the signature to decode the result");
+        return;
+    }
+
+    let decoded = hex::decode(response.data.trim_start_matches("0x"))
+        .map_err(|e| e.to_string())
+        .and_then(|data| options.function.decode_output(&data));
+
+    match decoded {
+        Ok(tokens) => {
+            println!("Decoded:");
+            for (param, token) in options.function.outputs.iter().zip(tokens) {
+                println!("  {}: {}", param.canonical(), token.to_json());
+            }
+        }
+        Err(e) => eprintln!("Error decoding result: {}", e),
+    }
+}
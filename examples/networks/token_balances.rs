@@ -0,0 +1,245 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/multicall.rs"]
+mod multicall;
+#[path = "../common/tokens.rs"]
+mod tokens;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::networks::types::ReadContractBodyNetwork,
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use std::{env, sync::Arc};
+use tokens::{MetadataCache, TokenClient};
+
+const DEFAULT_CACHE: &str = "token_metadata.json";
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    command: String,
+    args: Vec<String>,
+    network: ReadContractBodyNetwork,
+    cache: String,
+    batch_size: usize,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            command: args.next().unwrap_or_else(|| "balances".to_string()),
+            args: Vec::new(),
+            network: ReadContractBodyNetwork::Ethereum,
+            cache: DEFAULT_CACHE.to_string(),
+            batch_size: multicall::DEFAULT_CHUNK_SIZE,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--cache" => options.cache = value()?,
+                "--batch-size" => {
+                    options.batch_size = value()?.parse().map_err(|_| "invalid --batch-size")?
+                }
+                "--network" => {
+                    let name = value()?;
+                    options.network =
+                        serde_json::from_value(serde_json::Value::String(name.clone()))
+                            .map_err(|_| format!("unsupported network: {}", name))?;
+                }
+                _ => options.args.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+async fn balances(tokens: &mut TokenClient<'_>, args: &[String]) -> Result<(), String> {
+    let (holder, contracts) = args
+        .split_first()
+        .ok_or("usage: balances <holder> <token>...")?;
+
+    tokens.prefetch_metadata(contracts).await?;
+    let balances = tokens.balances_of(contracts, holder).await?;
+
+    println!("Token balances for {} on {:?}:", holder, tokens.network());
+    for (contract, balance) in contracts.iter().zip(balances) {
+        let metadata = tokens
+            .cache()
+            .get(tokens.network(), contract)
+            .cloned()
+            .unwrap_or_default();
+        match balance {
+            Ok(balance) => println!(
+                "  {:<10} {:>30}  {} ({})",
+                metadata.symbol.as_deref().unwrap_or("?"),
+                match metadata.decimals {
+                    Some(decimals) => evm::format_units(balance, decimals),
+                    None => format!("{} raw", balance),
+                },
+                metadata.name.as_deref().unwrap_or("unknown"),
+                contract
+            ),
+            Err(e) => eprintln!("  {}: {}", contract, e),
+        }
+    }
+    println!(
+        "Queried {} tokens in {} multicall batches",
+        contracts.len(),
+        contracts.len().div_ceil(tokens.chunk_size().max(1))
+    );
+
+    Ok(())
+}
+
+async fn info(tokens: &mut TokenClient<'_>, args: &[String]) -> Result<(), String> {
+    let contract = args.first().ok_or("usage: info <token>")?;
+    let metadata = tokens.metadata(contract).await?;
+    let supply = tokens.total_supply(contract).await?;
+
+    println!("Token {}:", contract);
+    println!("  Name: {}", metadata.name.as_deref().unwrap_or("unknown"));
+    println!(
+        "  Symbol: {}",
+        metadata.symbol.as_deref().unwrap_or("unknown")
+    );
+    println!("  Decimals: {:?}", metadata.decimals);
+    println!(
+        "  Total supply: {}",
+        tokens.format_amount(contract, supply).await?
+    );
+
+    Ok(())
+}
+
+async fn allowance(tokens: &mut TokenClient<'_>, args: &[String]) -> Result<(), String> {
+    let [contract, owner, spender] = args else {
+        return Err("usage: allowance <token> <owner> <spender>".to_string());
+    };
+    let amount = tokens.allowance(contract, owner, spender).await?;
+
+    println!(
+        "Allowance of {} for {}: {}",
+        owner,
+        spender,
+        tokens.format_amount(contract, amount).await?
+    );
+
+    Ok(())
+}
+
+async fn nft(tokens: &mut TokenClient<'_>, args: &[String]) -> Result<(), String> {
+    let [contract, token_id] = args else {
+        return Err("usage: nft <contract> <token-id>".to_string());
+    };
+    let token_id = abi::parse_uint(token_id)?;
+    let metadata = tokens.metadata(contract).await?;
+
+    println!(
+        "{} #{}:",
+        metadata.name.as_deref().unwrap_or(contract),
+        token_id
+    );
+    println!("  Owner: {}", tokens.owner_of(contract, token_id).await?);
+    match tokens.token_uri(contract, token_id).await {
+        Ok(uri) => println!("  Token URI: {}", uri),
+        Err(e) => println!("  Token URI: unavailable ({})", e),
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let cache = match MetadataCache::load(&options.cache) {
+        Ok(cache) => cache,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+    let mut tokens =
+        TokenClient::new(&client, options.network, cache).with_chunk_size(options.batch_size);
+
+    let result = match options.command.as_str() {
+        "balances" => balances(&mut tokens, &options.args).await,
+        "info" => info(&mut tokens, &options.args).await,
+        "allowance" => allowance(&mut tokens, &options.args).await,
+        "nft" => nft(&mut tokens, &options.args).await,
+        other => Err(format!(
+            "unknown command {} (expected balances, info, allowance or nft)",
+            other
+        )),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+    }
+
+    match tokens.cache().save() {
+        Ok(()) => println!(
+            "Cached metadata for {} tokens in {}",
+            tokens.cache().len(),
+            options.cache
+        ),
+        Err(e) => eprintln!("Error saving metadata cache: {}", e),
+    }
+}
@@ -0,0 +1,666 @@
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::permissions::types::{
+        ArchivePermissionRequest, ArchivePermissionRequestBody, CreateAssignmentRequest,
+        CreateAssignmentRequestBody, CreatePermissionRequest, CreatePermissionRequestBody,
+        DeleteAssignmentRequest, ListAssignmentsRequest, ListPermissionsRequest, Operation, Query,
+        UpdatePermissionRequest, UpdatePermissionRequestBody,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeSet, HashMap},
+    env, fs,
+    sync::Arc,
+};
+
+const DEFAULT_RBAC_FILE: &str = "examples/permissions/rbac.yaml";
+const IDENTITY_KINDS: [&str; 3] = ["user", "service-account", "pat"];
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RbacFile {
+    #[serde(default)]
+    roles: Vec<Role>,
+    #[serde(default)]
+    identities: Vec<Identity>,
+}
+
+#[derive(Deserialize)]
+struct Role {
+    name: String,
+    operations: BTreeSet<String>,
+}
+
+#[derive(Deserialize)]
+struct Identity {
+    id: String,
+    #[serde(default = "default_identity_kind")]
+    kind: String,
+    roles: Vec<String>,
+}
+
+fn default_identity_kind() -> String {
+    "user".to_string()
+}
+
+struct Options {
+    command: String,
+    path: String,
+    prune: bool,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            command: args.next().unwrap_or_else(|| "plan".to_string()),
+            path: DEFAULT_RBAC_FILE.to_string(),
+            prune: false,
+        };
+
+        for arg in args {
+            match arg.as_str() {
+                "--prune" => options.prune = true,
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown argument {}", flag));
+                }
+                path => options.path = path.to_string(),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct Permission {
+    id: String,
+    name: String,
+    operations: BTreeSet<String>,
+    is_immutable: bool,
+    pending_change_request: Option<String>,
+    assignments: Vec<Assignment>,
+}
+
+struct Assignment {
+    id: String,
+    identity_id: String,
+    is_immutable: bool,
+}
+
+enum Change {
+    CreatePermission {
+        name: String,
+        operations: BTreeSet<String>,
+    },
+    UpdatePermission {
+        id: String,
+        name: String,
+        operations: BTreeSet<String>,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    ArchivePermission {
+        id: String,
+        name: String,
+    },
+    CreateAssignment {
+        role: String,
+        permission_id: Option<String>,
+        identity_id: String,
+        kind: String,
+    },
+    DeleteAssignment {
+        role: String,
+        permission_id: String,
+        assignment_id: String,
+        identity_id: String,
+    },
+    Unchanged {
+        name: String,
+    },
+    Blocked {
+        name: String,
+        reason: String,
+    },
+}
+
+fn operation(name: &str) -> Result<Operation, String> {
+    serde_json::from_value(Value::String(name.to_string()))
+        .map_err(|_| format!("unknown operation {}", name))
+}
+
+fn load_file(path: &str) -> Result<RbacFile, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let file: RbacFile = serde_yaml::from_str(&contents).map_err(|e| format!("{}: {}", path, e))?;
+
+    let mut names = BTreeSet::new();
+    for role in &file.roles {
+        if !names.insert(role.name.as_str()) {
+            return Err(format!("{}: duplicate role {}", path, role.name));
+        }
+        for name in &role.operations {
+            operation(name).map_err(|e| format!("{}: role {}: {}", path, role.name, e))?;
+        }
+    }
+
+    let mut identities = BTreeSet::new();
+    for identity in &file.identities {
+        if !identities.insert(identity.id.as_str()) {
+            return Err(format!("{}: duplicate identity {}", path, identity.id));
+        }
+        if !IDENTITY_KINDS.contains(&identity.kind.as_str()) {
+            return Err(format!(
+                "{}: identity {} has unknown kind {} (expected one of {})",
+                path,
+                identity.id,
+                identity.kind,
+                IDENTITY_KINDS.join(", ")
+            ));
+        }
+        if let Some(role) = identity
+            .roles
+            .iter()
+            .find(|role| !names.contains(role.as_str()))
+        {
+            return Err(format!(
+                "{}: identity {} references undeclared role {}",
+                path, identity.id, role
+            ));
+        }
+    }
+
+    Ok(file)
+}
+
+fn strings(value: &Value) -> BTreeSet<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+async fn list_assignments(
+    client: &DfnsApiClient,
+    permission_id: &str,
+) -> Result<Vec<Assignment>, String> {
+    let response = client
+        .permissions()
+        .list_assignments(ListAssignmentsRequest {
+            permission_id: permission_id.to_string(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let page = serde_json::to_value(&response).map_err(|e| e.to_string())?;
+
+    Ok(page["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|item| Assignment {
+            id: item["id"].as_str().unwrap_or_default().to_string(),
+            identity_id: item["identityId"].as_str().unwrap_or_default().to_string(),
+            is_immutable: item["isImmutable"].as_bool().unwrap_or_default(),
+        })
+        .collect())
+}
+
+async fn list_permissions(client: &DfnsApiClient) -> Result<Vec<Permission>, String> {
+    let mut permissions = Vec::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListPermissionsRequest {
+            query: Some(Query {
+                limit: Some("100".to_string()),
+                pagination_token: pagination_token.take(),
+            }),
+        };
+
+        let response = client
+            .permissions()
+            .list_permissions(Some(request))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let page = serde_json::to_value(&response).map_err(|e| e.to_string())?;
+
+        for item in page["items"].as_array().into_iter().flatten() {
+            if item["isArchived"].as_bool().unwrap_or_default() {
+                continue;
+            }
+            let id = item["id"].as_str().unwrap_or_default().to_string();
+            permissions.push(Permission {
+                assignments: list_assignments(client, &id).await?,
+                id,
+                name: item["name"].as_str().unwrap_or_default().to_string(),
+                operations: strings(&item["operations"]),
+                is_immutable: item["isImmutable"].as_bool().unwrap_or_default(),
+                pending_change_request: item["pendingChangeRequest"]["id"]
+                    .as_str()
+                    .map(str::to_string),
+            });
+        }
+
+        match page["nextPageToken"].as_str() {
+            Some(token) => pagination_token = Some(token.to_string()),
+            None => break,
+        }
+    }
+
+    Ok(permissions)
+}
+
+fn plan_assignments(
+    role: &Role,
+    permission: Option<&Permission>,
+    identities: &[Identity],
+    prune: bool,
+    changes: &mut Vec<Change>,
+) {
+    let members: Vec<&Identity> = identities
+        .iter()
+        .filter(|identity| identity.roles.contains(&role.name))
+        .collect();
+    let existing = permission.map(|permission| permission.assignments.as_slice());
+
+    for identity in &members {
+        if existing
+            .into_iter()
+            .flatten()
+            .any(|assignment| assignment.identity_id == identity.id)
+        {
+            continue;
+        }
+        changes.push(Change::CreateAssignment {
+            role: role.name.clone(),
+            permission_id: permission.map(|permission| permission.id.clone()),
+            identity_id: identity.id.clone(),
+            kind: identity.kind.clone(),
+        });
+    }
+
+    let Some(permission) = permission else {
+        return;
+    };
+    for assignment in &permission.assignments {
+        if members
+            .iter()
+            .any(|identity| identity.id == assignment.identity_id)
+        {
+            continue;
+        }
+        if assignment.is_immutable {
+            changes.push(Change::Blocked {
+                name: format!("{} / {}", role.name, assignment.identity_id),
+                reason: "assignment is immutable".to_string(),
+            });
+            continue;
+        }
+        if !prune {
+            changes.push(Change::Blocked {
+                name: format!("{} / {}", role.name, assignment.identity_id),
+                reason: format!(
+                    "{} is not declared (pass --prune to revoke it)",
+                    assignment.id
+                ),
+            });
+            continue;
+        }
+        changes.push(Change::DeleteAssignment {
+            role: role.name.clone(),
+            permission_id: permission.id.clone(),
+            assignment_id: assignment.id.clone(),
+            identity_id: assignment.identity_id.clone(),
+        });
+    }
+}
+
+fn plan(file: &RbacFile, existing: &[Permission], prune: bool) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for role in &file.roles {
+        let matches: Vec<&Permission> = existing
+            .iter()
+            .filter(|permission| permission.name == role.name)
+            .collect();
+
+        let permission = match matches.as_slice() {
+            [] => {
+                changes.push(Change::CreatePermission {
+                    name: role.name.clone(),
+                    operations: role.operations.clone(),
+                });
+                None
+            }
+            [current] if current.operations == role.operations => {
+                changes.push(Change::Unchanged {
+                    name: role.name.clone(),
+                });
+                Some(*current)
+            }
+            [current] if current.is_immutable => {
+                changes.push(Change::Blocked {
+                    name: role.name.clone(),
+                    reason: format!("{} is immutable", current.id),
+                });
+                Some(*current)
+            }
+            [current] => {
+                match &current.pending_change_request {
+                    Some(request) => changes.push(Change::Blocked {
+                        name: role.name.clone(),
+                        reason: format!("change request {} is still pending", request),
+                    }),
+                    None => changes.push(Change::UpdatePermission {
+                        id: current.id.clone(),
+                        name: role.name.clone(),
+                        operations: role.operations.clone(),
+                        added: role
+                            .operations
+                            .difference(&current.operations)
+                            .cloned()
+                            .collect(),
+                        removed: current
+                            .operations
+                            .difference(&role.operations)
+                            .cloned()
+                            .collect(),
+                    }),
+                }
+                Some(*current)
+            }
+            _ => {
+                changes.push(Change::Blocked {
+                    name: role.name.clone(),
+                    reason: format!("{} active permissions share this name", matches.len()),
+                });
+                continue;
+            }
+        };
+
+        plan_assignments(role, permission, &file.identities, prune, &mut changes);
+    }
+
+    for current in existing {
+        if current.is_immutable || file.roles.iter().any(|role| role.name == current.name) {
+            continue;
+        }
+        if prune {
+            changes.push(Change::ArchivePermission {
+                id: current.id.clone(),
+                name: current.name.clone(),
+            });
+        } else {
+            changes.push(Change::Blocked {
+                name: current.name.clone(),
+                reason: format!(
+                    "{} is not declared (pass --prune to archive it)",
+                    current.id
+                ),
+            });
+        }
+    }
+
+    changes
+}
+
+fn print_plan(changes: &[Change]) {
+    let (mut permissions, mut assign, mut revoke) = (0, 0, 0);
+
+    for change in changes {
+        match change {
+            Change::CreatePermission { name, operations } => {
+                permissions += 1;
+                println!("+ create    {} ({} operations)", name, operations.len());
+                for operation in operations {
+                    println!("    + {}", operation);
+                }
+            }
+            Change::UpdatePermission {
+                id,
+                name,
+                added,
+                removed,
+                ..
+            } => {
+                permissions += 1;
+                println!("~ update    {} ({})", name, id);
+                for operation in added {
+                    println!("    + {}", operation);
+                }
+                for operation in removed {
+                    println!("    - {}", operation);
+                }
+            }
+            Change::ArchivePermission { id, name } => {
+                permissions += 1;
+                println!("- archive   {} ({})", name, id);
+            }
+            Change::CreateAssignment {
+                role,
+                identity_id,
+                kind,
+                ..
+            } => {
+                assign += 1;
+                println!("+ assign    {} {} -> {}", kind, identity_id, role);
+            }
+            Change::DeleteAssignment {
+                role,
+                assignment_id,
+                identity_id,
+                ..
+            } => {
+                revoke += 1;
+                println!(
+                    "- revoke    {} -> {} ({})",
+                    identity_id, role, assignment_id
+                );
+            }
+            Change::Unchanged { name } => println!("= unchanged {}", name),
+            Change::Blocked { name, reason } => println!("! skip      {}: {}", name, reason),
+        }
+    }
+
+    println!(
+        "\nPlan: {} permission changes, {} assignments to create, {} to delete",
+        permissions, assign, revoke
+    );
+}
+
+async fn apply(client: &DfnsApiClient, changes: Vec<Change>) -> Result<(), String> {
+    let mut created: HashMap<String, String> = HashMap::new();
+    let mut failed = 0;
+
+    for change in changes {
+        let result = match change {
+            Change::CreatePermission { name, operations } => {
+                let request = CreatePermissionRequest {
+                    body: CreatePermissionRequestBody {
+                        name,
+                        operations: operations
+                            .iter()
+                            .map(|name| operation(name))
+                            .collect::<Result<_, _>>()?,
+                    },
+                };
+                client
+                    .permissions()
+                    .create_permission(request)
+                    .await
+                    .map(|response| {
+                        created.insert(response.name.clone(), response.id.clone());
+                        format!("Created {} -> {}", response.name, response.id)
+                    })
+            }
+            Change::UpdatePermission { id, operations, .. } => {
+                let request = UpdatePermissionRequest {
+                    permission_id: id,
+                    body: UpdatePermissionRequestBody {
+                        name: None,
+                        operations: Some(
+                            operations
+                                .iter()
+                                .map(|name| operation(name))
+                                .collect::<Result<_, _>>()?,
+                        ),
+                    },
+                };
+                client
+                    .permissions()
+                    .update_permission(request)
+                    .await
+                    .map(|response| format!("Updated {} -> {}", response.name, response.id))
+            }
+            Change::ArchivePermission { id, .. } => client
+                .permissions()
+                .archive_permission(ArchivePermissionRequest {
+                    permission_id: id,
+                    body: ArchivePermissionRequestBody { is_archived: true },
+                })
+                .await
+                .map(|response| format!("Archived {} -> {}", response.name, response.id)),
+            Change::CreateAssignment {
+                role,
+                permission_id,
+                identity_id,
+                ..
+            } => {
+                let Some(permission_id) = permission_id.or_else(|| created.get(&role).cloned())
+                else {
+                    eprintln!("Error: {} was not created, skipping {}", role, identity_id);
+                    failed += 1;
+                    continue;
+                };
+                client
+                    .permissions()
+                    .create_assignment(CreateAssignmentRequest {
+                        permission_id,
+                        body: CreateAssignmentRequestBody { identity_id },
+                    })
+                    .await
+                    .map(|response| {
+                        format!(
+                            "Assigned {} -> {} ({})",
+                            response.identity_id, role, response.id
+                        )
+                    })
+            }
+            Change::DeleteAssignment {
+                role,
+                permission_id,
+                assignment_id,
+                identity_id,
+            } => client
+                .permissions()
+                .delete_assignment(DeleteAssignmentRequest {
+                    permission_id,
+                    assignment_id,
+                })
+                .await
+                .map(|_| format!("Revoked {} -> {}", identity_id, role)),
+            Change::Unchanged { .. } | Change::Blocked { .. } => continue,
+        };
+
+        match result {
+            Ok(message) => println!("{}", message),
+            Err(e) => {
+                eprintln!("Error: {:?}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} changes failed", failed));
+    }
+    Ok(())
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let apply_changes = match options.command.as_str() {
+        "plan" => false,
+        "apply" => true,
+        other => {
+            return Err(format!(
+                "unknown command {} (expected plan or apply)",
+                other
+            ));
+        }
+    };
+
+    let file = load_file(&options.path)?;
+    let existing = list_permissions(client).await?;
+    let changes = plan(&file, &existing, options.prune);
+
+    print_plan(&changes);
+
+    if !apply_changes {
+        return Ok(());
+    }
+    println!();
+    apply(client, changes).await
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
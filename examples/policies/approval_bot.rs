@@ -0,0 +1,378 @@
+#[path = "../common/approvals.rs"]
+mod approvals;
+
+use approvals::Approval;
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    collections::HashSet,
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    sync::Arc,
+    time::Duration,
+};
+
+const DEFAULT_RULES: &str = "examples/policies/approval_bot.toml";
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct BotConfig {
+    approver_id: String,
+    #[serde(default = "default_interval")]
+    interval_secs: u64,
+    #[serde(default = "default_audit_log")]
+    audit_log: String,
+    rules: Vec<Rule>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Verdict {
+    Approve,
+    Deny,
+}
+
+#[derive(Deserialize)]
+struct Rule {
+    name: String,
+    decision: Verdict,
+    #[serde(default)]
+    activity_kinds: Vec<String>,
+    #[serde(default)]
+    wallets: Vec<String>,
+    #[serde(default)]
+    networks: Vec<String>,
+    #[serde(default)]
+    destinations: Vec<String>,
+    #[serde(default)]
+    not_destinations: Vec<String>,
+    #[serde(default)]
+    assets: Vec<String>,
+    max_amount: Option<String>,
+    min_amount: Option<String>,
+    #[serde(default)]
+    initiators: Vec<String>,
+    #[serde(default)]
+    exclude_initiators: Vec<String>,
+    hours_utc: Option<[u8; 2]>,
+    #[serde(default)]
+    weekdays_only: bool,
+}
+
+fn default_interval() -> u64 {
+    30
+}
+
+fn default_audit_log() -> String {
+    "approval_audit.jsonl".to_string()
+}
+
+fn contains(list: &[String], value: Option<&str>) -> bool {
+    value.is_some_and(|value| list.iter().any(|item| item.eq_ignore_ascii_case(value)))
+}
+
+fn amount(value: Option<&str>) -> Option<u128> {
+    value.and_then(|value| value.parse().ok())
+}
+
+impl Rule {
+    fn matches(&self, approval: &Approval, now: u64) -> bool {
+        if !self.activity_kinds.is_empty()
+            && !contains(&self.activity_kinds, approval.activity_kind.as_deref())
+        {
+            return false;
+        }
+        if !self.wallets.is_empty() && !contains(&self.wallets, approval.wallet_id.as_deref()) {
+            return false;
+        }
+        if !self.networks.is_empty() && !contains(&self.networks, approval.network.as_deref()) {
+            return false;
+        }
+        if !self.destinations.is_empty() && !contains(&self.destinations, approval.to.as_deref()) {
+            return false;
+        }
+        if contains(&self.not_destinations, approval.to.as_deref())
+            || (!self.not_destinations.is_empty() && approval.to.is_none())
+        {
+            return false;
+        }
+        if !self.initiators.is_empty() && !self.initiators.contains(&approval.initiator_id) {
+            return false;
+        }
+        if self.exclude_initiators.contains(&approval.initiator_id) {
+            return false;
+        }
+        if !self.assets.is_empty() && !contains(&self.assets, approval.asset.as_deref()) {
+            return false;
+        }
+
+        let value = amount(approval.amount.as_deref());
+        if let Some(max) = amount(self.max_amount.as_deref())
+            && value.is_none_or(|value| value > max)
+        {
+            return false;
+        }
+        if let Some(min) = amount(self.min_amount.as_deref())
+            && value.is_none_or(|value| value < min)
+        {
+            return false;
+        }
+
+        let days = now / 86_400;
+        let hour = (now % 86_400 / 3600) as u8;
+        if self.weekdays_only && (days + 3) % 7 >= 5 {
+            return false;
+        }
+        if let Some([start, end]) = self.hours_utc
+            && !(start..end).contains(&hour)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+#[derive(Default)]
+struct BotState {
+    decided: HashSet<String>,
+    deferred: HashSet<String>,
+}
+
+struct Options {
+    rules: String,
+    dry_run: bool,
+    once: bool,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut options = Options {
+            rules: DEFAULT_RULES.to_string(),
+            dry_run: false,
+            once: false,
+        };
+
+        for arg in env::args().skip(1) {
+            match arg.as_str() {
+                "--dry-run" => options.dry_run = true,
+                "--once" => options.once = true,
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown argument {}", flag));
+                }
+                path => options.rules = path.to_string(),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn load_config(path: &str) -> Result<BotConfig, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let config: BotConfig = toml::from_str(&contents).map_err(|e| format!("{}: {}", path, e))?;
+    for rule in &config.rules {
+        for limit in [&rule.max_amount, &rule.min_amount].into_iter().flatten() {
+            if amount(Some(limit.as_str())).is_none() {
+                return Err(format!(
+                    "{}: rule {} has invalid amount {}",
+                    path, rule.name, limit
+                ));
+            }
+            if rule.assets.is_empty() {
+                return Err(format!(
+                    "{}: rule {} sets an amount bound without assets to scope its units",
+                    path, rule.name
+                ));
+            }
+            if rule.networks.is_empty() && contains(&rule.assets, Some("Native")) {
+                return Err(format!(
+                    "{}: rule {} bounds a Native amount without networks to scope its units",
+                    path, rule.name
+                ));
+            }
+        }
+    }
+    Ok(config)
+}
+
+fn audit(path: &str, entry: serde_json::Value) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("failed to open {}: {}", path, e))?;
+    writeln!(file, "{}", entry).map_err(|e| format!("failed to write {}: {}", path, e))
+}
+
+async fn process(
+    client: &DfnsApiClient,
+    config: &BotConfig,
+    options: &Options,
+    state: &mut BotState,
+) -> Result<(), String> {
+    let pending = approvals::list_pending(client, Some(&config.approver_id)).await?;
+    let now = approvals::unix_now();
+
+    for approval in pending {
+        if state.decided.contains(&approval.id) || approval.decided_by(&config.approver_id) {
+            continue;
+        }
+
+        if approval.initiator_id == config.approver_id {
+            if state.deferred.insert(approval.id.clone()) {
+                println!("{}: skipped, initiated by this approver", approval.id);
+            }
+            continue;
+        }
+
+        let Some(rule) = config
+            .rules
+            .iter()
+            .find(|rule| rule.matches(&approval, now))
+        else {
+            if !state.deferred.insert(approval.id.clone()) {
+                continue;
+            }
+            println!("{}: no rule matched, left for manual review", approval.id);
+            audit(
+                &config.audit_log,
+                json!({
+                    "timestamp": now,
+                    "approvalId": approval.id,
+                    "summary": approval.summary(),
+                    "initiatorId": approval.initiator_id,
+                    "decision": null,
+                    "outcome": "manual",
+                }),
+            )?;
+            continue;
+        };
+
+        state.decided.insert(approval.id.clone());
+        let approve = rule.decision == Verdict::Approve;
+        let reason = format!("approval bot rule: {}", rule.name);
+        let outcome = if options.dry_run {
+            "dry-run".to_string()
+        } else {
+            match approvals::decide(client, &approval.id, approve, &reason).await {
+                Ok(status) => format!("submitted ({})", status),
+                Err(e) => {
+                    state.decided.remove(&approval.id);
+                    format!("error: {}", e)
+                }
+            }
+        };
+
+        println!(
+            "{}: {} by rule {:?} - {} [{}]",
+            approval.id,
+            if approve { "approve" } else { "deny" },
+            rule.name,
+            approval.summary(),
+            outcome
+        );
+        audit(
+            &config.audit_log,
+            json!({
+                "timestamp": now,
+                "approvalId": approval.id,
+                "summary": approval.summary(),
+                "initiatorId": approval.initiator_id,
+                "walletId": approval.wallet_id,
+                "to": approval.to,
+                "amount": approval.amount,
+                "rule": rule.name,
+                "decision": if approve { "Approved" } else { "Denied" },
+                "reason": reason,
+                "outcome": outcome,
+            }),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let config = match load_config(&options.rules) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error loading rules: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    println!(
+        "Approval bot for {} with {} rules, audit log {}",
+        config.approver_id,
+        config.rules.len(),
+        config.audit_log
+    );
+
+    let mut state = BotState::default();
+    loop {
+        if let Err(e) = process(&client, &config, &options, &mut state).await {
+            eprintln!("Error: {}", e);
+        }
+        if options.once {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(config.interval_secs)).await;
+    }
+}
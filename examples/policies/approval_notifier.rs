@@ -0,0 +1,642 @@
+#[path = "../common/approvals.rs"]
+mod approvals;
+
+use approvals::Approval;
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::webhooks::types::{
+        Kind, ListWebhookEventsRequest, ListWebhookEventsRequestQuery,
+        ListWebhookEventsResponseItem,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    sync::Arc,
+    time::Duration,
+};
+
+const DEFAULT_CONFIG: &str = "examples/policies/approval_notifier.toml";
+const FIELDS: [&str; 15] = [
+    "id",
+    "summary",
+    "activity",
+    "wallet",
+    "network",
+    "to",
+    "amount",
+    "asset",
+    "initiator",
+    "policies",
+    "decisions",
+    "created",
+    "expires",
+    "age",
+    "remaining",
+];
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Source {
+    #[default]
+    Poll,
+    Events,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Stage {
+    New,
+    Reminder,
+    Escalation,
+}
+
+#[derive(Deserialize)]
+struct Templates {
+    #[serde(default = "default_new")]
+    new: String,
+    #[serde(default = "default_reminder")]
+    reminder: String,
+    #[serde(default = "default_escalation")]
+    escalation: String,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Self {
+            new: default_new(),
+            reminder: default_reminder(),
+            escalation: default_escalation(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Endpoint {
+    name: String,
+    url: String,
+    #[serde(default = "all_stages")]
+    stages: Vec<Stage>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default = "default_message_field")]
+    message_field: String,
+    body: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NotifierConfig {
+    #[serde(default)]
+    source: Source,
+    webhook_id: Option<String>,
+    approver_id: Option<String>,
+    #[serde(default = "default_interval")]
+    interval_secs: u64,
+    #[serde(default = "default_state_file")]
+    state_file: String,
+    reminder_every_mins: Option<u64>,
+    escalate_before_mins: Option<u64>,
+    #[serde(default)]
+    templates: Templates,
+    endpoints: Vec<Endpoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Tracked {
+    first_seen: u64,
+    last_notified: u64,
+    reminders: u32,
+    escalated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    partial: Option<Delivery>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Delivery {
+    stage: Stage,
+    endpoints: BTreeSet<String>,
+}
+
+#[derive(Default)]
+struct EventFeed {
+    pending: BTreeMap<String, Approval>,
+    pending_cursor: Option<String>,
+    resolved_cursor: Option<String>,
+}
+
+fn default_new() -> String {
+    "New approval {id} pending: {summary}, initiated by {initiator}, expires {expires}".to_string()
+}
+
+fn default_reminder() -> String {
+    "Reminder: approval {id} pending for {age}: {summary}".to_string()
+}
+
+fn default_escalation() -> String {
+    "ESCALATION: approval {id} is auto-rejected in {remaining}: {summary}".to_string()
+}
+
+fn all_stages() -> Vec<Stage> {
+    vec![Stage::New, Stage::Reminder, Stage::Escalation]
+}
+
+fn default_message_field() -> String {
+    "text".to_string()
+}
+
+fn default_interval() -> u64 {
+    60
+}
+
+fn default_state_file() -> String {
+    "approval_notifier_state.json".to_string()
+}
+
+struct Options {
+    config: String,
+    dry_run: bool,
+    once: bool,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut options = Options {
+            config: DEFAULT_CONFIG.to_string(),
+            dry_run: false,
+            once: false,
+        };
+
+        for arg in env::args().skip(1) {
+            match arg.as_str() {
+                "--dry-run" => options.dry_run = true,
+                "--once" => options.once = true,
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown argument {}", flag));
+                }
+                path => options.config = path.to_string(),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn load_config(path: &str) -> Result<NotifierConfig, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let config: NotifierConfig =
+        toml::from_str(&contents).map_err(|e| format!("{}: {}", path, e))?;
+    if config.source == Source::Events && config.webhook_id.is_none() {
+        return Err(format!("{}: source = \"events\" requires webhook_id", path));
+    }
+    if config.endpoints.is_empty() {
+        return Err(format!("{}: no endpoints configured", path));
+    }
+    let placeholders: Vec<_> = FIELDS.iter().map(|name| (*name, "-".to_string())).collect();
+    for endpoint in &config.endpoints {
+        payload(endpoint, "-", &placeholders).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(config)
+}
+
+fn load_state(path: &str) -> Result<BTreeMap<String, Tracked>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|e| format!("{}: {}", path, e)),
+        Err(_) => Ok(BTreeMap::new()),
+    }
+}
+
+fn save_state(path: &str, state: &BTreeMap<String, Tracked>) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| format!("failed to write {}: {}", path, e))
+}
+
+fn human(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        _ => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+fn fields(approval: &Approval, now: u64) -> Vec<(&'static str, String)> {
+    let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    let policies = approval
+        .triggered_policies
+        .iter()
+        .map(|policy| policy.policy_id.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let decisions = approval
+        .decisions
+        .iter()
+        .map(|decision| format!("{} by {}", decision.value, decision.user_id))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let values = [
+        approval.id.clone(),
+        approval.summary(),
+        optional(&approval.activity_kind),
+        optional(&approval.wallet_id),
+        optional(&approval.network),
+        optional(&approval.to),
+        optional(&approval.amount),
+        optional(&approval.asset),
+        approval.initiator_id.clone(),
+        policies,
+        decisions,
+        optional(&approval.date_created),
+        optional(&approval.expiration_date),
+        approval
+            .created_at()
+            .map(|created| human(now.saturating_sub(created)))
+            .unwrap_or_else(|| "-".to_string()),
+        approval
+            .expires_at()
+            .map(|expires| human(expires.saturating_sub(now)))
+            .unwrap_or_else(|| "-".to_string()),
+    ];
+    FIELDS.into_iter().zip(values).collect()
+}
+
+fn render(template: &str, fields: &[(&str, String)], escape: fn(&str) -> String) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let field = rest[1..].find('}').and_then(|end| {
+            let key = &rest[1..end + 1];
+            fields
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| (end + 2, value))
+        });
+        match field {
+            Some((len, value)) => {
+                text.push_str(&escape(value));
+                rest = &rest[len..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+fn payload(endpoint: &Endpoint, message: &str, fields: &[(&str, String)]) -> Result<Value, String> {
+    let Some(body) = &endpoint.body else {
+        let mut payload = Map::new();
+        payload.insert(
+            endpoint.message_field.clone(),
+            Value::String(message.to_string()),
+        );
+        return Ok(Value::Object(payload));
+    };
+    let mut fields = fields.to_vec();
+    fields.push(("message", message.to_string()));
+    serde_json::from_str(&render(body, &fields, json_escape))
+        .map_err(|e| format!("endpoint {} body is not valid JSON: {}", endpoint.name, e))
+}
+
+async fn post(http: &reqwest::Client, endpoint: &Endpoint, payload: &Value) -> Result<(), String> {
+    let mut request = http.post(&endpoint.url).json(payload);
+    for (name, value) in &endpoint.headers {
+        request = request.header(name, value);
+    }
+    request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+async fn new_events(
+    client: &DfnsApiClient,
+    webhook_id: &str,
+    kind: Kind,
+    cursor: Option<&str>,
+) -> Result<Vec<ListWebhookEventsResponseItem>, String> {
+    let mut events = Vec::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListWebhookEventsRequest {
+            webhook_id: webhook_id.to_string(),
+            query: Some(ListWebhookEventsRequestQuery {
+                delivery_failed: None,
+                kind: Some(kind.clone()),
+                limit: Some(100.0),
+                pagination_token: pagination_token.take(),
+            }),
+        };
+        let response = client
+            .webhooks()
+            .list_webhook_events(request)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        for event in response.items {
+            if cursor == Some(event.id.as_str()) {
+                return Ok(events);
+            }
+            events.push(event);
+        }
+
+        match response.next_page_token {
+            Some(token) => pagination_token = Some(token),
+            None => return Ok(events),
+        }
+    }
+}
+
+fn event_approval(event: &ListWebhookEventsResponseItem) -> Value {
+    event
+        .data
+        .get("approval")
+        .cloned()
+        .flatten()
+        .unwrap_or_default()
+}
+
+async fn pending_from_events(
+    client: &DfnsApiClient,
+    webhook_id: &str,
+    feed: &mut EventFeed,
+) -> Result<Vec<Approval>, String> {
+    let pending = new_events(
+        client,
+        webhook_id,
+        Kind::PolicyApprovalPending,
+        feed.pending_cursor.as_deref(),
+    )
+    .await?;
+    let resolved = new_events(
+        client,
+        webhook_id,
+        Kind::PolicyApprovalResolved,
+        feed.resolved_cursor.as_deref(),
+    )
+    .await?;
+
+    if let Some(event) = pending.first() {
+        feed.pending_cursor = Some(event.id.clone());
+    }
+    if let Some(event) = resolved.first() {
+        feed.resolved_cursor = Some(event.id.clone());
+    }
+
+    for event in pending.iter().rev() {
+        match Approval::from_json(event_approval(event)) {
+            Ok(approval) => {
+                feed.pending.insert(approval.id.clone(), approval);
+            }
+            Err(e) => eprintln!("Skipping webhook event {}: {}", event.id, e),
+        }
+    }
+    for event in &resolved {
+        if let Some(id) = event_approval(event)["id"].as_str() {
+            feed.pending.remove(id);
+        }
+    }
+
+    let now = approvals::unix_now();
+    feed.pending
+        .retain(|_, approval| approval.expires_at().is_none_or(|expires| expires > now));
+    Ok(feed.pending.values().cloned().collect())
+}
+
+fn next_stage(
+    config: &NotifierConfig,
+    tracked: Option<&Tracked>,
+    approval: &Approval,
+    now: u64,
+) -> Option<Stage> {
+    let expiring = config.escalate_before_mins.is_some_and(|minutes| {
+        approval
+            .expires_at()
+            .is_some_and(|expires| expires.saturating_sub(now) <= minutes * 60)
+    });
+
+    match tracked {
+        _ if expiring && !tracked.is_some_and(|tracked| tracked.escalated) => {
+            Some(Stage::Escalation)
+        }
+        None => Some(Stage::New),
+        Some(Tracked {
+            partial: Some(partial),
+            ..
+        }) => Some(partial.stage),
+        Some(tracked) => config
+            .reminder_every_mins
+            .filter(|minutes| now.saturating_sub(tracked.last_notified) >= minutes * 60)
+            .map(|_| Stage::Reminder),
+    }
+}
+
+async fn process(
+    client: &DfnsApiClient,
+    http: &reqwest::Client,
+    config: &NotifierConfig,
+    options: &Options,
+    state: &mut BTreeMap<String, Tracked>,
+    feed: &mut EventFeed,
+) -> Result<(), String> {
+    let pending = match (&config.source, &config.webhook_id) {
+        (Source::Events, Some(webhook_id)) => pending_from_events(client, webhook_id, feed).await?,
+        _ => approvals::list_pending(client, config.approver_id.as_deref()).await?,
+    };
+    let now = approvals::unix_now();
+
+    state.retain(|id, _| pending.iter().any(|approval| &approval.id == id));
+
+    for approval in &pending {
+        if config
+            .approver_id
+            .as_deref()
+            .is_some_and(|approver_id| approval.decided_by(approver_id))
+        {
+            continue;
+        }
+        let Some(stage) = next_stage(config, state.get(&approval.id), approval, now) else {
+            continue;
+        };
+
+        let template = match stage {
+            Stage::New => &config.templates.new,
+            Stage::Reminder => &config.templates.reminder,
+            Stage::Escalation => &config.templates.escalation,
+        };
+        let fields = fields(approval, now);
+        let message = render(template, &fields, str::to_string);
+        println!("{}", message);
+
+        let mut delivered = state
+            .get_mut(&approval.id)
+            .and_then(|tracked| tracked.partial.take())
+            .filter(|partial| partial.stage == stage)
+            .map(|partial| partial.endpoints)
+            .unwrap_or_default();
+        let mut complete = true;
+        let remaining: Vec<_> = config
+            .endpoints
+            .iter()
+            .filter(|endpoint| {
+                endpoint.stages.contains(&stage) && !delivered.contains(&endpoint.name)
+            })
+            .collect();
+        for endpoint in remaining {
+            let result = match payload(endpoint, &message, &fields) {
+                Ok(payload) if options.dry_run => {
+                    println!("  would post to {}: {}", endpoint.name, payload);
+                    Ok(())
+                }
+                Ok(payload) => post(http, endpoint, &payload)
+                    .await
+                    .map(|()| println!("  posted to {}", endpoint.name)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => {
+                    delivered.insert(endpoint.name.clone());
+                }
+                Err(e) => {
+                    eprintln!("  failed to post to {}: {}", endpoint.name, e);
+                    complete = false;
+                }
+            }
+        }
+        if !complete && delivered.is_empty() {
+            continue;
+        }
+
+        let tracked = state.entry(approval.id.clone()).or_insert(Tracked {
+            first_seen: now,
+            last_notified: now,
+            reminders: 0,
+            escalated: false,
+            partial: None,
+        });
+        if !complete {
+            tracked.partial = Some(Delivery {
+                stage,
+                endpoints: delivered,
+            });
+            continue;
+        }
+        tracked.last_notified = now;
+        match stage {
+            Stage::New => {}
+            Stage::Reminder => tracked.reminders += 1,
+            Stage::Escalation => tracked.escalated = true,
+        }
+    }
+
+    if options.dry_run {
+        return Ok(());
+    }
+    save_state(&config.state_file, state)
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let config = match load_config(&options.config) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+    let http = reqwest::Client::new();
+
+    let mut state = match load_state(&config.state_file) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("Error loading state: {}", e);
+            return;
+        }
+    };
+
+    let mut feed = EventFeed::default();
+
+    println!(
+        "Approval notifier with {} endpoints, state in {}",
+        config.endpoints.len(),
+        config.state_file
+    );
+
+    loop {
+        if let Err(e) = process(&client, &http, &config, &options, &mut state, &mut feed).await {
+            eprintln!("Error: {}", e);
+        }
+        if options.once {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(config.interval_secs)).await;
+    }
+}
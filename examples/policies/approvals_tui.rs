@@ -0,0 +1,427 @@
+#[path = "../common/approvals.rs"]
+mod approvals;
+#[path = "../common/policies.rs"]
+mod policies;
+
+use approvals::Approval;
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+use std::{
+    collections::HashMap,
+    env,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const DEFAULT_REFRESH: Duration = Duration::from_secs(15);
+const POLL_TIMEOUT: Duration = Duration::from_millis(250);
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    approver_id: Option<String>,
+    refresh: Duration,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            approver_id: None,
+            refresh: DEFAULT_REFRESH,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--approver" => options.approver_id = Some(value()?),
+                "--refresh" => {
+                    options.refresh =
+                        Duration::from_secs(value()?.parse().map_err(|_| "invalid --refresh")?)
+                }
+                other => return Err(format!("unknown argument {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+enum Mode {
+    Browse,
+    Reason { approve: bool, input: String },
+}
+
+enum Command {
+    None,
+    Quit,
+    Refresh,
+    Submit { approve: bool, reason: String },
+}
+
+struct App {
+    approvals: Vec<Approval>,
+    policy_names: HashMap<String, String>,
+    list: ListState,
+    mode: Mode,
+    status: String,
+    last_refresh: Instant,
+    approver_id: Option<String>,
+}
+
+impl App {
+    fn new(approver_id: Option<String>, policy_names: HashMap<String, String>) -> Self {
+        Self {
+            approvals: Vec::new(),
+            policy_names,
+            list: ListState::default(),
+            mode: Mode::Browse,
+            status: String::new(),
+            last_refresh: Instant::now(),
+            approver_id,
+        }
+    }
+
+    fn selected(&self) -> Option<&Approval> {
+        self.list
+            .selected()
+            .and_then(|index| self.approvals.get(index))
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        if self.approvals.is_empty() {
+            return;
+        }
+        let current = self.list.selected().unwrap_or(0) as isize;
+        let last = self.approvals.len() as isize - 1;
+        self.list
+            .select(Some((current + offset).clamp(0, last) as usize));
+    }
+
+    async fn refresh(&mut self, client: &DfnsApiClient) {
+        let selected = self.selected().map(|approval| approval.id.clone());
+        match approvals::list_pending(client, self.approver_id.as_deref()).await {
+            Ok(approvals) => {
+                self.approvals = approvals;
+                let index = selected
+                    .and_then(|id| self.approvals.iter().position(|approval| approval.id == id))
+                    .or((!self.approvals.is_empty()).then_some(0));
+                self.list.select(index);
+                self.status = format!("{} pending approvals", self.approvals.len());
+            }
+            Err(e) => self.status = format!("Refresh failed: {}", e),
+        }
+        self.last_refresh = Instant::now();
+    }
+
+    async fn submit(&mut self, client: &DfnsApiClient, approve: bool, reason: String) {
+        let Some(approval_id) = self.selected().map(|approval| approval.id.clone()) else {
+            return;
+        };
+        match approvals::decide(client, &approval_id, approve, &reason).await {
+            Ok(status) => {
+                self.refresh(client).await;
+                self.status = format!(
+                    "{} {}: now {}",
+                    if approve { "Approved" } else { "Denied" },
+                    approval_id,
+                    status
+                );
+            }
+            Err(e) => self.status = format!("Decision on {} failed: {}", approval_id, e),
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> Command {
+        match &mut self.mode {
+            Mode::Browse => match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Command::Quit,
+                KeyCode::Char('r') => return Command::Refresh,
+                KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+                KeyCode::Char(key @ ('a' | 'd')) if self.selected().is_some() => {
+                    self.mode = Mode::Reason {
+                        approve: key == 'a',
+                        input: String::new(),
+                    };
+                }
+                _ => {}
+            },
+            Mode::Reason { approve, input } => match code {
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(key) => input.push(key),
+                KeyCode::Enter if !*approve && input.trim().is_empty() => {
+                    self.status = "A reason is required to deny".to_string();
+                }
+                KeyCode::Enter => {
+                    let command = Command::Submit {
+                        approve: *approve,
+                        reason: input.trim().to_string(),
+                    };
+                    self.mode = Mode::Browse;
+                    return command;
+                }
+                _ => {}
+            },
+        }
+        Command::None
+    }
+}
+
+fn field<'a>(label: &'a str, value: Option<&'a str>) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(
+            format!("{:<12}", label),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(value.unwrap_or("-")),
+    ])
+}
+
+fn details<'a>(app: &'a App, approval: &'a Approval) -> Vec<Line<'a>> {
+    let mut lines = vec![
+        field("Approval", Some(&approval.id)),
+        field("Activity", approval.activity_kind.as_deref()),
+        field("Request", approval.request_kind.as_deref()),
+        field("Wallet", approval.wallet_id.as_deref()),
+        field("Network", approval.network.as_deref()),
+        field("Destination", approval.to.as_deref()),
+        field("Amount", approval.amount.as_deref()),
+        field("Asset", approval.asset.as_deref()),
+        field("Initiator", Some(&approval.initiator_id)),
+        field("Created", approval.date_created.as_deref()),
+        field("Expires", approval.expiration_date.as_deref()),
+        Line::default(),
+        Line::styled(
+            "Triggered policies",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+    ];
+
+    for policy in &approval.triggered_policies {
+        let name = app
+            .policy_names
+            .get(&policy.policy_id)
+            .map(String::as_str)
+            .unwrap_or(&policy.policy_id);
+        let reason = policy
+            .reason
+            .as_deref()
+            .map(|reason| format!(" ({})", reason))
+            .unwrap_or_default();
+        lines.push(Line::raw(format!("  {}{}", name, reason)));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled(
+        "Decisions",
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    if approval.decisions.is_empty() {
+        lines.push(Line::raw("  none yet"));
+    }
+    for decision in &approval.decisions {
+        let color = if decision.value == "Approved" {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<9}", decision.value),
+                Style::default().fg(color),
+            ),
+            Span::raw(format!(
+                "{} at {}{}",
+                decision.user_id,
+                decision.date,
+                decision
+                    .reason
+                    .as_deref()
+                    .map(|reason| format!(": {}", reason))
+                    .unwrap_or_default()
+            )),
+        ]));
+    }
+    lines
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(frame.area());
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+    let items: Vec<ListItem> = app
+        .approvals
+        .iter()
+        .map(|approval| {
+            ListItem::new(format!(
+                "{}  {} -> {}",
+                approval.id,
+                approval.amount.as_deref().unwrap_or("?"),
+                approval.to.as_deref().unwrap_or("?")
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Pending approvals "),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, left, &mut app.list);
+
+    let detail = match app.selected() {
+        Some(approval) => details(app, approval),
+        None => vec![Line::raw("No pending approvals")],
+    };
+    frame.render_widget(
+        Paragraph::new(detail)
+            .block(Block::default().borders(Borders::ALL).title(" Details "))
+            .wrap(Wrap { trim: false }),
+        right,
+    );
+
+    let footer_text = match &app.mode {
+        Mode::Browse => format!(
+            "{}  |  up/down select  a approve  d deny  r refresh  q quit",
+            app.status
+        ),
+        Mode::Reason { approve, input } => format!(
+            "Reason to {}: {}_  (Enter submit, Esc cancel)",
+            if *approve { "approve" } else { "deny" },
+            input
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(footer_text).block(Block::default().borders(Borders::ALL)),
+        footer,
+    );
+}
+
+async fn run(
+    terminal: &mut DefaultTerminal,
+    client: &DfnsApiClient,
+    app: &mut App,
+    refresh: Duration,
+) -> Result<(), String> {
+    app.refresh(client).await;
+
+    loop {
+        terminal
+            .draw(|frame| draw(frame, app))
+            .map_err(|e| e.to_string())?;
+
+        let mut command = Command::None;
+        if event::poll(POLL_TIMEOUT).map_err(|e| e.to_string())?
+            && let Event::Key(key) = event::read().map_err(|e| e.to_string())?
+            && key.kind == KeyEventKind::Press
+        {
+            command = app.handle_key(key.code);
+        }
+
+        match command {
+            Command::Quit => return Ok(()),
+            Command::Refresh => app.refresh(client).await,
+            Command::Submit { approve, reason } => app.submit(client, approve, reason).await,
+            Command::None => {
+                if matches!(app.mode, Mode::Browse) && app.last_refresh.elapsed() >= refresh {
+                    app.refresh(client).await;
+                }
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let policy_names = policies::list_policies(&client)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|policy| {
+            Some((
+                policy["id"].as_str()?.to_string(),
+                policies::policy_name(policy)?.to_string(),
+            ))
+        })
+        .collect();
+
+    let mut app = App::new(options.approver_id, policy_names);
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &client, &mut app, options.refresh).await;
+    ratatui::restore();
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+    }
+}
@@ -0,0 +1,266 @@
+#[path = "../common/policy_rules.rs"]
+mod policy_rules;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::policies::types::{ActivityKindEnum, CreatePolicyBody, CreatePolicyRequest},
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use policy_rules::{
+    Action, AlertLevel, Alerts, ApprovalGroup, Categories, ChainalysisPrescreening,
+    ChainalysisScreening, Currency, FallbackBehaviours, Filters, Rule,
+};
+use std::{env, sync::Arc, time::Duration};
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    create: bool,
+    approvers: Vec<String>,
+    wallet_tag: String,
+    category_ids: Vec<u32>,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            create: false,
+            approvers: vec![
+                "us-example-approver-1".to_string(),
+                "us-example-approver-2".to_string(),
+                "us-example-approver-3".to_string(),
+            ],
+            wallet_tag: "treasury".to_string(),
+            category_ids: Vec::new(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--create" => options.create = true,
+                "--approvers" => {
+                    options.approvers = value()?.split(',').map(str::to_string).collect()
+                }
+                "--wallet-tag" => options.wallet_tag = value()?,
+                "--category-ids" => {
+                    options.category_ids = value()?
+                        .split(',')
+                        .map(|id| id.trim().parse())
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| "invalid --category-ids")?
+                }
+                "--exclude-initiator" => {
+                    return Err(
+                        "--exclude-initiator is not supported: approval groups in dfns-sdk-rs \
+                         0.1.0 only carry name, quorum and approvers, so the initiator \
+                         exclusion would be dropped from the request"
+                            .to_string(),
+                    );
+                }
+                other => return Err(format!("unknown argument {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct PolicyDraft {
+    name: &'static str,
+    activity_kind: ActivityKindEnum,
+    rule: Rule,
+    action: Action,
+    filters: Option<Filters>,
+}
+
+impl PolicyDraft {
+    fn into_body(self) -> Result<CreatePolicyBody, String> {
+        let label = |e: String| format!("{}: {}", self.name, e);
+        Ok(CreatePolicyBody {
+            name: self.name.to_string(),
+            activity_kind: self.activity_kind,
+            rule: self.rule.to_body().map_err(label)?,
+            action: self.action.to_body().map_err(label)?,
+            filters: self
+                .filters
+                .as_ref()
+                .map(Filters::to_body)
+                .transpose()
+                .map_err(label)?,
+        })
+    }
+}
+
+fn drafts(options: &Options) -> Result<Vec<PolicyDraft>, String> {
+    let treasury = ApprovalGroup::new(2)
+        .name("Treasury")
+        .approvers(&options.approvers);
+    let compliance = ApprovalGroup::new(1).name("Compliance");
+    let tagged = Filters::wallet_tags([options.wallet_tag.as_str()]);
+
+    Ok(vec![
+        PolicyDraft {
+            name: "Approve transfers above 10k USD",
+            activity_kind: ActivityKindEnum::WalletsSign,
+            rule: Rule::amount_limit(10_000.0, Currency::Usd),
+            action: Action::request_approval()
+                .group(treasury.clone())
+                .auto_reject_timeout_minutes(60),
+            filters: Some(tagged.clone()),
+        },
+        PolicyDraft {
+            name: "Approve more than 50k USD per day",
+            activity_kind: ActivityKindEnum::WalletsSign,
+            rule: Rule::amount_velocity(50_000.0, Currency::Usd, Duration::from_secs(24 * 3600))?,
+            action: Action::request_approval()
+                .group(treasury.clone())
+                .group(compliance.clone()),
+            filters: Some(tagged.clone()),
+        },
+        PolicyDraft {
+            name: "Block more than 20 transfers per hour",
+            activity_kind: ActivityKindEnum::WalletsSign,
+            rule: Rule::count_velocity(20, Duration::from_secs(3600))?,
+            action: Action::Block,
+            filters: None,
+        },
+        PolicyDraft {
+            name: "Approve transfers to unknown recipients",
+            activity_kind: ActivityKindEnum::WalletsSign,
+            rule: Rule::recipient_whitelist([
+                "0x1234567890abcdef1234567890abcdef12345678",
+                "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd",
+            ]),
+            action: Action::request_approval().group(treasury.clone()),
+            filters: Some(tagged),
+        },
+        PolicyDraft {
+            name: "Prescreen outgoing transfers",
+            activity_kind: ActivityKindEnum::WalletsSign,
+            rule: ChainalysisPrescreening::new(
+                Alerts::new(AlertLevel::High).categories(options.category_ids.clone()),
+                FallbackBehaviours::default(),
+            )
+            .direct_exposures(Categories::new(options.category_ids.clone()))
+            .addresses(Categories::new(options.category_ids.clone()))
+            .build(),
+            action: Action::request_approval().group(compliance.clone()),
+            filters: None,
+        },
+        PolicyDraft {
+            name: "Screen incoming transfers",
+            activity_kind: ActivityKindEnum::WalletsIncomingTransaction,
+            rule: ChainalysisScreening::new(
+                Alerts::new(AlertLevel::Severe),
+                FallbackBehaviours::skip_all(),
+            )
+            .direct_exposures(Categories::new(options.category_ids.clone()))
+            .build(),
+            action: Action::request_approval().group(compliance),
+            filters: None,
+        },
+        PolicyDraft {
+            name: "Review policy changes",
+            activity_kind: ActivityKindEnum::PoliciesModify,
+            rule: Rule::AlwaysTrigger,
+            action: Action::request_approval().group(treasury),
+            filters: None,
+        },
+    ])
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    for draft in drafts(options)? {
+        println!(
+            "{} ({} -> {})",
+            draft.name,
+            draft.rule.kind(),
+            draft.action.kind()
+        );
+        println!("  rule: {}", draft.rule.to_json()?);
+        println!("  action: {}", draft.action.to_json()?);
+        if let Some(filters) = &draft.filters {
+            println!("  filters: {}", filters.to_json()?);
+        }
+
+        let body = draft.into_body()?;
+        if !options.create {
+            continue;
+        }
+
+        match client
+            .policies()
+            .create_policy(CreatePolicyRequest { body })
+            .await
+        {
+            Ok(response) => println!("  Created {} ({})", response.id, response.status),
+            Err(e) => eprintln!("  Error creating policy: {:?}", e),
+        }
+    }
+
+    if !options.create {
+        println!("\nDry run: pass --create to create these policies");
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
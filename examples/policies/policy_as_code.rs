@@ -0,0 +1,336 @@
+#[path = "../common/policies.rs"]
+mod policies;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::policies::types::{
+        ArchivePolicyRequest, CreatePolicyBody, CreatePolicyRequest, UpdatePolicyBody,
+        UpdatePolicyRequest,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use serde_json::Value;
+use std::{env, sync::Arc};
+
+const DEFAULT_POLICY_FILE: &str = "examples/policies/policies.yaml";
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    command: String,
+    path: String,
+    prune: bool,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            command: args.next().unwrap_or_else(|| "plan".to_string()),
+            path: DEFAULT_POLICY_FILE.to_string(),
+            prune: false,
+        };
+
+        for arg in args {
+            match arg.as_str() {
+                "--prune" => options.prune = true,
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown argument {}", flag));
+                }
+                path => options.path = path.to_string(),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+enum Change {
+    Create {
+        desired: Value,
+    },
+    Update {
+        id: String,
+        desired: Value,
+        diffs: Vec<(&'static str, Value, Value)>,
+    },
+    Archive {
+        id: String,
+        name: String,
+    },
+    Unchanged {
+        name: String,
+    },
+    Blocked {
+        name: String,
+        reason: String,
+    },
+}
+
+fn summary(policy: &Value) -> String {
+    format!(
+        "{}, {} -> {}",
+        policy["activityKind"].as_str().unwrap_or("?"),
+        policy["rule"]["kind"].as_str().unwrap_or("?"),
+        policy["action"]["kind"].as_str().unwrap_or("?")
+    )
+}
+
+fn plan(desired: Vec<Value>, existing: &[Value], prune: bool) -> Result<Vec<Change>, String> {
+    let mut changes = Vec::new();
+
+    for policy in desired {
+        policies::body::<CreatePolicyBody>(&policy)?;
+        let name = policies::policy_name(&policy)
+            .unwrap_or_default()
+            .to_string();
+        let matches: Vec<&Value> = existing
+            .iter()
+            .filter(|current| policies::policy_name(current) == Some(name.as_str()))
+            .collect();
+
+        let change = match matches.as_slice() {
+            [] => Change::Create { desired: policy },
+            [current] => {
+                let id = current["id"].as_str().unwrap_or_default().to_string();
+                let diffs = policies::changed_fields(current, &policy);
+                if diffs.is_empty() {
+                    Change::Unchanged { name }
+                } else if let Some(request) = current["pendingChangeRequest"]["id"].as_str() {
+                    Change::Blocked {
+                        name,
+                        reason: format!("change request {} is still pending", request),
+                    }
+                } else {
+                    Change::Update {
+                        id,
+                        desired: policy,
+                        diffs,
+                    }
+                }
+            }
+            _ => Change::Blocked {
+                name,
+                reason: format!("{} active policies share this name", matches.len()),
+            },
+        };
+        changes.push(change);
+    }
+
+    let declared: Vec<String> = changes
+        .iter()
+        .filter_map(|change| match change {
+            Change::Create { desired } | Change::Update { desired, .. } => {
+                policies::policy_name(desired).map(str::to_string)
+            }
+            Change::Unchanged { name } | Change::Blocked { name, .. } => Some(name.clone()),
+            Change::Archive { .. } => None,
+        })
+        .collect();
+
+    for current in existing {
+        let name = policies::policy_name(current).unwrap_or_default();
+        if declared.iter().any(|declared| declared == name) {
+            continue;
+        }
+        let id = current["id"].as_str().unwrap_or_default().to_string();
+        if prune {
+            changes.push(Change::Archive {
+                id,
+                name: name.to_string(),
+            });
+        } else {
+            changes.push(Change::Blocked {
+                name: name.to_string(),
+                reason: format!("{} is not declared (pass --prune to archive it)", id),
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+fn print_plan(changes: &[Change]) {
+    let (mut create, mut update, mut archive) = (0, 0, 0);
+
+    for change in changes {
+        match change {
+            Change::Create { desired } => {
+                create += 1;
+                println!(
+                    "+ create    {} ({})",
+                    policies::policy_name(desired).unwrap_or_default(),
+                    summary(desired)
+                );
+            }
+            Change::Update { id, desired, diffs } => {
+                update += 1;
+                println!(
+                    "~ update    {} ({})",
+                    policies::policy_name(desired).unwrap_or_default(),
+                    id
+                );
+                for (field, before, after) in diffs {
+                    println!("    {}: {} -> {}", field, before, after);
+                }
+            }
+            Change::Archive { id, name } => {
+                archive += 1;
+                println!("- archive   {} ({})", name, id);
+            }
+            Change::Unchanged { name } => println!("= unchanged {}", name),
+            Change::Blocked { name, reason } => println!("! skip      {}: {}", name, reason),
+        }
+    }
+
+    println!(
+        "\nPlan: {} to create, {} to update, {} to archive",
+        create, update, archive
+    );
+}
+
+async fn apply(client: &DfnsApiClient, changes: Vec<Change>) -> Result<(), String> {
+    let mut failed = 0;
+
+    for change in changes {
+        let result = match change {
+            Change::Create { desired } => {
+                let request = CreatePolicyRequest {
+                    body: policies::body(&desired)?,
+                };
+                client
+                    .policies()
+                    .create_policy(request)
+                    .await
+                    .map(|response| {
+                        format!(
+                            "Created {} -> {} ({})",
+                            response.name, response.id, response.status
+                        )
+                    })
+            }
+            Change::Update { id, desired, .. } => {
+                let body: UpdatePolicyBody = policies::body(&desired)?;
+                client
+                    .policies()
+                    .update_policy(UpdatePolicyRequest {
+                        policy_id: id,
+                        body,
+                    })
+                    .await
+                    .map(|response| {
+                        format!(
+                            "Updated {} -> {} ({})",
+                            response.name, response.id, response.status
+                        )
+                    })
+            }
+            Change::Archive { id, .. } => client
+                .policies()
+                .archive_policy(ArchivePolicyRequest { policy_id: id })
+                .await
+                .map(|response| {
+                    format!(
+                        "Archived {} -> {} ({})",
+                        response.name, response.id, response.status
+                    )
+                }),
+            Change::Unchanged { .. } | Change::Blocked { .. } => continue,
+        };
+
+        match result {
+            Ok(message) => println!("{}", message),
+            Err(e) => {
+                eprintln!("Error: {:?}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} changes failed", failed));
+    }
+    Ok(())
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let apply_changes = match options.command.as_str() {
+        "plan" => false,
+        "apply" => true,
+        other => {
+            return Err(format!(
+                "unknown command {} (expected plan or apply)",
+                other
+            ));
+        }
+    };
+
+    let desired = policies::load_file(&options.path)?;
+    let existing = policies::active_policies(client).await?;
+    let changes = plan(desired, &existing, options.prune)?;
+
+    print_plan(&changes);
+
+    if !apply_changes {
+        return Ok(());
+    }
+    println!();
+    apply(client, changes).await
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
@@ -0,0 +1,432 @@
+#[path = "../common/policies.rs"]
+mod policies;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        auth::types::GetUserRequest,
+        wallets::types::{ListWalletsRequest, ListWalletsRequestQuery},
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use serde_json::{Value, json};
+use std::{collections::BTreeMap, env, fs, sync::Arc};
+
+const COVERING_KINDS: &[&str] = &["Wallets:Sign"];
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    json: Option<String>,
+    activity_kinds: Vec<String>,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            json: None,
+            activity_kinds: Vec::new(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--json" => options.json = Some(value()?),
+                "--activity-kind" => options.activity_kinds.push(value()?),
+                other => return Err(format!("unknown argument {}", other)),
+            }
+        }
+
+        if options.activity_kinds.is_empty() {
+            options.activity_kinds = COVERING_KINDS.iter().map(|kind| kind.to_string()).collect();
+        }
+        Ok(options)
+    }
+}
+
+struct WalletInfo {
+    id: String,
+    name: String,
+    network: String,
+    tags: Vec<String>,
+}
+
+struct UserInfo {
+    username: String,
+    is_active: bool,
+}
+
+struct GroupFinding {
+    policy: String,
+    group: String,
+    quorum: u64,
+    inactive: Vec<String>,
+    missing: Vec<String>,
+    active: usize,
+    restricted: bool,
+}
+
+impl GroupFinding {
+    fn has_gap(&self) -> bool {
+        !self.inactive.is_empty() || !self.missing.is_empty() || !self.quorum_reachable()
+    }
+
+    fn quorum_reachable(&self) -> bool {
+        !self.restricted || self.active as u64 >= self.quorum
+    }
+}
+
+async fn list_wallets(client: &DfnsApiClient) -> Result<Vec<WalletInfo>, String> {
+    let mut wallets = Vec::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListWalletsRequest {
+            query: Some(ListWalletsRequestQuery {
+                limit: Some("100".to_string()),
+                pagination_token: pagination_token.take(),
+                owner_id: None,
+                owner_username: None,
+            }),
+        };
+
+        let response = client
+            .wallets()
+            .list_wallets(Some(request))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        wallets.extend(response.items.into_iter().map(|wallet| WalletInfo {
+            id: wallet.id,
+            name: wallet.name.unwrap_or_default(),
+            network: format!("{:?}", wallet.network),
+            tags: wallet.tags,
+        }));
+
+        match response.next_page_token {
+            Some(token) => pagination_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(wallets)
+}
+
+fn has_wallet_filters(policy: &Value) -> bool {
+    let filters = &policy["filters"];
+    !filters["walletId"].is_null() || !filters["walletTags"].is_null()
+}
+
+fn approvers(group: &Value) -> Vec<String> {
+    group["approvers"]["userId"]["in"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+async fn lookup_users(
+    client: &DfnsApiClient,
+    policies: &[Value],
+) -> BTreeMap<String, Option<UserInfo>> {
+    let mut users = BTreeMap::new();
+    let groups = policies.iter().flat_map(|policy| {
+        policy["action"]["approvalGroups"]
+            .as_array()
+            .into_iter()
+            .flatten()
+    });
+
+    for user_id in groups.flat_map(approvers) {
+        if users.contains_key(&user_id) {
+            continue;
+        }
+        let request = GetUserRequest {
+            user_id: user_id.clone(),
+        };
+        let user = match client.auth().get_user(request).await {
+            Ok(user) => Some(UserInfo {
+                username: user.username,
+                is_active: user.is_active,
+            }),
+            Err(e) => {
+                eprintln!("Could not look up user {}: {:?}", user_id, e);
+                None
+            }
+        };
+        users.insert(user_id, user);
+    }
+
+    users
+}
+
+fn group_findings(
+    policies: &[Value],
+    users: &BTreeMap<String, Option<UserInfo>>,
+) -> Vec<GroupFinding> {
+    let mut findings = Vec::new();
+
+    for policy in policies {
+        let name = policies::policy_name(policy).unwrap_or("<unnamed>");
+        let groups = policy["action"]["approvalGroups"]
+            .as_array()
+            .into_iter()
+            .flatten();
+        for (index, group) in groups.enumerate() {
+            let approvers = approvers(group);
+            let (mut inactive, mut missing) = (Vec::new(), Vec::new());
+            for user_id in &approvers {
+                match users.get(user_id) {
+                    Some(Some(user)) if !user.is_active => {
+                        inactive.push(format!("{} ({})", user_id, user.username))
+                    }
+                    Some(Some(_)) => {}
+                    _ => missing.push(user_id.clone()),
+                }
+            }
+            let active = approvers.len() - inactive.len() - missing.len();
+
+            findings.push(GroupFinding {
+                policy: name.to_string(),
+                group: group["name"]
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("group {}", index + 1)),
+                quorum: group["quorum"]
+                    .as_f64()
+                    .map(|quorum| quorum as u64)
+                    .unwrap_or(1),
+                inactive,
+                missing,
+                active,
+                restricted: !approvers.is_empty(),
+            });
+        }
+    }
+
+    findings
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let policies = policies::active_policies(client).await?;
+    let wallets = list_wallets(client).await?;
+    let users = lookup_users(client, &policies).await;
+
+    let covering: Vec<&Value> = policies
+        .iter()
+        .filter(|policy| {
+            let kind = policy["activityKind"].as_str().unwrap_or_default();
+            options.activity_kinds.iter().any(|covered| covered == kind)
+                && policy["action"]["kind"].as_str() != Some("NoAction")
+        })
+        .collect();
+
+    let coverage: Vec<(&WalletInfo, Vec<&str>)> = wallets
+        .iter()
+        .map(|wallet| {
+            let names = covering
+                .iter()
+                .filter(|policy| policies::matches_wallet(policy, &wallet.id, &wallet.tags))
+                .map(|policy| policies::policy_name(policy).unwrap_or("<unnamed>"))
+                .collect();
+            (wallet, names)
+        })
+        .collect();
+
+    let unmatched: Vec<&Value> = policies
+        .iter()
+        .filter(|policy| {
+            policy["activityKind"]
+                .as_str()
+                .is_some_and(|kind| kind.starts_with("Wallets:"))
+                && has_wallet_filters(policy)
+                && !wallets
+                    .iter()
+                    .any(|wallet| policies::matches_wallet(policy, &wallet.id, &wallet.tags))
+        })
+        .collect();
+
+    let findings = group_findings(&policies, &users);
+    let gaps: Vec<&GroupFinding> = findings
+        .iter()
+        .filter(|finding| finding.has_gap())
+        .collect();
+    let uncovered = coverage
+        .iter()
+        .filter(|(_, names)| names.is_empty())
+        .count();
+
+    println!(
+        "Coverage of {} wallets by {} active {} policies\n",
+        wallets.len(),
+        covering.len(),
+        options.activity_kinds.join("/")
+    );
+    println!(
+        "{:<28} {:<20} {:<16} {:<24} POLICIES",
+        "WALLET", "NAME", "NETWORK", "TAGS"
+    );
+    for (wallet, names) in &coverage {
+        println!(
+            "{:<28} {:<20} {:<16} {:<24} {}",
+            wallet.id,
+            wallet.name,
+            wallet.network,
+            wallet.tags.join(","),
+            if names.is_empty() {
+                "NOT COVERED".to_string()
+            } else {
+                names.join(", ")
+            }
+        );
+    }
+
+    println!(
+        "\nPolicies with wallet filters matching no wallet: {}",
+        unmatched.len()
+    );
+    for policy in &unmatched {
+        println!(
+            "  {} ({}) filters: {}",
+            policies::policy_name(policy).unwrap_or("<unnamed>"),
+            policy["id"].as_str().unwrap_or("?"),
+            policy["filters"]
+        );
+    }
+
+    println!(
+        "\nApproval groups with inactive or unknown approvers: {}",
+        gaps.len()
+    );
+    for finding in &gaps {
+        println!(
+            "  {} / {}: quorum {}, {} active approvers{}",
+            finding.policy,
+            finding.group,
+            finding.quorum,
+            finding.active,
+            if finding.quorum_reachable() {
+                ""
+            } else {
+                " - QUORUM UNREACHABLE"
+            }
+        );
+        for user in &finding.inactive {
+            println!("    deactivated: {}", user);
+        }
+        for user in &finding.missing {
+            println!("    not found:   {}", user);
+        }
+    }
+
+    println!(
+        "\nSummary: {} uncovered wallets, {} unmatched policies, {} approval group issues",
+        uncovered,
+        unmatched.len(),
+        gaps.len()
+    );
+
+    if let Some(path) = &options.json {
+        let report = json!({
+            "activityKinds": options.activity_kinds,
+            "summary": {
+                "wallets": wallets.len(),
+                "activePolicies": policies.len(),
+                "coveringPolicies": covering.len(),
+                "uncoveredWallets": uncovered,
+                "unmatchedPolicies": unmatched.len(),
+                "approvalGroupIssues": gaps.len(),
+            },
+            "wallets": coverage.iter().map(|(wallet, names)| json!({
+                "id": wallet.id,
+                "name": wallet.name,
+                "network": wallet.network,
+                "tags": wallet.tags,
+                "policies": names,
+                "covered": !names.is_empty(),
+            })).collect::<Vec<_>>(),
+            "unmatchedPolicies": unmatched.iter().map(|policy| json!({
+                "id": policy["id"],
+                "name": policy["name"],
+                "activityKind": policy["activityKind"],
+                "filters": policy["filters"],
+            })).collect::<Vec<_>>(),
+            "approvalGroups": findings.iter().map(|finding| json!({
+                "policy": finding.policy,
+                "group": finding.group,
+                "quorum": finding.quorum,
+                "activeApprovers": finding.active,
+                "deactivatedApprovers": finding.inactive,
+                "unknownApprovers": finding.missing,
+                "quorumReachable": finding.quorum_reachable(),
+            })).collect::<Vec<_>>(),
+        });
+        let contents = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        fs::write(path, contents).map_err(|e| format!("failed to write {}: {}", path, e))?;
+        println!("JSON report written to {}", path);
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
@@ -0,0 +1,383 @@
+#[path = "../common/policies.rs"]
+mod policies;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::GetWalletRequest,
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use serde_json::Value;
+use std::{env, sync::Arc};
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Activity {
+    kind: String,
+    wallet_id: Option<String>,
+    tags: Option<Vec<String>>,
+    to: Option<String>,
+    value_usd: Option<f64>,
+    value_eur: Option<f64>,
+    recent_usd: f64,
+    recent_eur: f64,
+    recent_count: Option<u64>,
+}
+
+struct Options {
+    activity: Activity,
+    policies: Option<String>,
+}
+
+fn parse_amount(value: &str, flag: &str) -> Result<f64, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|amount: &f64| amount.is_finite() && *amount >= 0.0)
+        .ok_or_else(|| format!("invalid {}", flag))
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let command = args.next().unwrap_or_else(|| "transfer".to_string());
+        let kind = match command.as_str() {
+            "transfer" | "signature" => "Wallets:Sign",
+            "create-wallet" => {
+                return Err(
+                    "create-wallet cannot be simulated: wallet creation is not a policy \
+                     activity kind (Dfns policies cover Wallets:Sign, \
+                     Wallets:IncomingTransaction, Permissions:* and Policies:Modify)"
+                        .to_string(),
+                );
+            }
+            other => {
+                return Err(format!(
+                    "unknown activity {} (expected transfer or signature)",
+                    other
+                ));
+            }
+        };
+
+        let mut options = Options {
+            activity: Activity {
+                kind: kind.to_string(),
+                wallet_id: None,
+                tags: None,
+                to: None,
+                value_usd: None,
+                value_eur: None,
+                recent_usd: 0.0,
+                recent_eur: 0.0,
+                recent_count: None,
+            },
+            policies: None,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            let activity = &mut options.activity;
+            match arg.as_str() {
+                "--activity-kind" => activity.kind = value()?,
+                "--to" => activity.to = Some(value()?),
+                "--tags" => activity.tags = Some(value()?.split(',').map(str::to_string).collect()),
+                "--value-usd" => activity.value_usd = Some(parse_amount(&value()?, &arg)?),
+                "--value-eur" => activity.value_eur = Some(parse_amount(&value()?, &arg)?),
+                "--recent-usd" => activity.recent_usd = parse_amount(&value()?, &arg)?,
+                "--recent-eur" => activity.recent_eur = parse_amount(&value()?, &arg)?,
+                "--recent-count" => {
+                    activity.recent_count =
+                        Some(value()?.parse().map_err(|_| "invalid --recent-count")?)
+                }
+                "--policies" => options.policies = Some(value()?),
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown argument {}", flag));
+                }
+                wallet_id => activity.wallet_id = Some(wallet_id.to_string()),
+            }
+        }
+
+        if options.activity.wallet_id.is_none() {
+            return Err(format!("usage: {} <wallet-id> [options]", command));
+        }
+        Ok(options)
+    }
+}
+
+enum Trigger {
+    Yes(String),
+    No(String),
+    Unknown(String),
+}
+
+impl Activity {
+    fn value_in(&self, currency: &str) -> Option<f64> {
+        match currency {
+            "USD" => self.value_usd,
+            "EUR" => self.value_eur,
+            _ => None,
+        }
+    }
+
+    fn recent_in(&self, currency: &str) -> f64 {
+        match currency {
+            "EUR" => self.recent_eur,
+            _ => self.recent_usd,
+        }
+    }
+
+    fn evaluate(&self, rule: &Value) -> Trigger {
+        let configuration = &rule["configuration"];
+        let currency = configuration["currency"].as_str().unwrap_or("USD");
+        let limit = configuration["limit"].as_f64().unwrap_or_default();
+
+        match rule["kind"].as_str().unwrap_or_default() {
+            "AlwaysTrigger" => Trigger::Yes("always triggers".to_string()),
+            "TransactionAmountLimit" => match self.value_in(currency) {
+                Some(value) if value > limit => Trigger::Yes(format!(
+                    "{} {} exceeds {} {}",
+                    value, currency, limit, currency
+                )),
+                Some(value) => Trigger::No(format!("{} {} within {}", value, currency, limit)),
+                None => Trigger::Unknown(format!("needs --value-{}", currency.to_lowercase())),
+            },
+            "TransactionAmountVelocity" => match self.value_in(currency) {
+                Some(value) => {
+                    let total = self.recent_in(currency) + value;
+                    let message = format!(
+                        "{} {} over {} minutes against {}",
+                        total, currency, configuration["timeframe"], limit
+                    );
+                    if total > limit {
+                        Trigger::Yes(message)
+                    } else {
+                        Trigger::No(message)
+                    }
+                }
+                None => Trigger::Unknown(format!(
+                    "needs --value-{} and --recent-{}",
+                    currency.to_lowercase(),
+                    currency.to_lowercase()
+                )),
+            },
+            "TransactionCountVelocity" => match self.recent_count {
+                Some(recent) => {
+                    let message = format!(
+                        "{} transfers over {} minutes against {}",
+                        recent + 1,
+                        configuration["timeframe"],
+                        limit
+                    );
+                    if (recent + 1) as f64 > limit {
+                        Trigger::Yes(message)
+                    } else {
+                        Trigger::No(message)
+                    }
+                }
+                None => Trigger::Unknown("needs --recent-count".to_string()),
+            },
+            "TransactionRecipientWhitelist" => {
+                let Some(to) = &self.to else {
+                    return Trigger::Unknown("needs --to".to_string());
+                };
+                let whitelisted = configuration["addresses"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .any(|address| address.eq_ignore_ascii_case(to));
+                if whitelisted {
+                    Trigger::No(format!("{} is whitelisted", to))
+                } else {
+                    Trigger::Yes(format!("{} is not whitelisted", to))
+                }
+            }
+            kind if kind.starts_with("Chainalysis") => {
+                Trigger::Unknown("depends on the Chainalysis screening result".to_string())
+            }
+            kind => Trigger::Unknown(format!("rule kind {} is not simulated", kind)),
+        }
+    }
+}
+
+fn approval_groups(action: &Value) -> String {
+    action["approvalGroups"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|group| {
+            format!(
+                "{} (quorum {})",
+                group["name"].as_str().unwrap_or("unnamed group"),
+                group["quorum"]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+async fn load_policies(client: &DfnsApiClient, options: &Options) -> Result<Vec<Value>, String> {
+    match &options.policies {
+        Some(path) => policies::load_file(path),
+        None => policies::active_policies(client).await,
+    }
+}
+
+async fn wallet_tags(client: &DfnsApiClient, activity: &Activity) -> Result<Vec<String>, String> {
+    if let Some(tags) = &activity.tags {
+        return Ok(tags.clone());
+    }
+    let Some(wallet_id) = &activity.wallet_id else {
+        return Ok(Vec::new());
+    };
+
+    let wallet = client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: wallet_id.clone(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let wallet = serde_json::to_value(&wallet).map_err(|e| e.to_string())?;
+    Ok(wallet["tags"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect())
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let activity = &options.activity;
+    let policies = load_policies(client, options).await?;
+    let tags = wallet_tags(client, activity).await?;
+    let wallet_id = activity.wallet_id.as_deref().unwrap_or_default();
+
+    println!(
+        "Simulating {} against {} policies{}",
+        activity.kind,
+        policies.len(),
+        if tags.is_empty() {
+            String::new()
+        } else {
+            format!(" (wallet tags: {})", tags.join(", "))
+        }
+    );
+
+    let (mut blocked, mut approvals, mut uncertain) = (Vec::new(), Vec::new(), Vec::new());
+    let mut matched = 0;
+
+    for policy in &policies {
+        let name = policies::policy_name(policy).unwrap_or("<unnamed>");
+        if policy["activityKind"].as_str() != Some(activity.kind.as_str()) {
+            continue;
+        }
+        if activity.wallet_id.is_some() && !policies::matches_wallet(policy, wallet_id, &tags) {
+            println!("  skip     {}: filters do not match this wallet", name);
+            continue;
+        }
+        matched += 1;
+
+        let action = policy["action"]["kind"].as_str().unwrap_or("NoAction");
+        let rule = policy["rule"]["kind"].as_str().unwrap_or("?");
+        match activity.evaluate(&policy["rule"]) {
+            Trigger::Yes(reason) => {
+                println!("  trigger  {} [{}]: {} -> {}", name, rule, reason, action);
+                match action {
+                    "Block" => blocked.push(name),
+                    "RequestApproval" => {
+                        approvals.push(format!("{}: {}", name, approval_groups(&policy["action"])))
+                    }
+                    _ => {}
+                }
+            }
+            Trigger::No(reason) => println!("  pass     {} [{}]: {}", name, rule, reason),
+            Trigger::Unknown(reason) => {
+                println!("  unknown  {} [{}]: {} -> {}", name, rule, reason, action);
+                if action != "NoAction" {
+                    uncertain.push(format!("{} ({})", name, action));
+                }
+            }
+        }
+    }
+
+    println!("\n{} policies matched the activity", matched);
+    if !blocked.is_empty() {
+        println!("Outcome: BLOCKED by {}", blocked.join(", "));
+    } else if !approvals.is_empty() {
+        println!("Outcome: requires approval");
+        for approval in &approvals {
+            println!("  {}", approval);
+        }
+    } else {
+        println!("Outcome: executes without approval");
+    }
+    if !uncertain.is_empty() {
+        println!(
+            "Could not be decided locally, may still apply: {}",
+            uncertain.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
@@ -0,0 +1,408 @@
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::{
+        ListWalletsRequest, ListWalletsRequestQuery, TagWalletRequest, TagWalletRequestBody,
+        UntagWalletRequest, UntagWalletRequestBody, UpdateWalletRequest, UpdateWalletRequestBody,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use std::{
+    env,
+    io::{self, Write},
+    sync::Arc,
+};
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+#[derive(Default)]
+struct Selector {
+    networks: Vec<String>,
+    name_pattern: Option<String>,
+    tags: Vec<String>,
+    external_id_prefix: Option<String>,
+}
+
+impl Selector {
+    fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+            && self.name_pattern.is_none()
+            && self.tags.is_empty()
+            && self.external_id_prefix.is_none()
+    }
+
+    fn matches(&self, wallet: &WalletInfo) -> bool {
+        let network_matches = self.networks.is_empty()
+            || self
+                .networks
+                .iter()
+                .any(|network| network.eq_ignore_ascii_case(&wallet.network));
+        let name_matches = self
+            .name_pattern
+            .as_deref()
+            .is_none_or(|pattern| wildcard_match(pattern, &wallet.name));
+        let tags_match = self.tags.iter().all(|tag| wallet.tags.contains(tag));
+        let external_id_matches = self.external_id_prefix.as_deref().is_none_or(|prefix| {
+            wallet
+                .external_id
+                .as_deref()
+                .is_some_and(|external_id| external_id.starts_with(prefix))
+        });
+
+        network_matches && name_matches && tags_match && external_id_matches
+    }
+}
+
+#[derive(Default)]
+struct Operations {
+    add_tags: Vec<String>,
+    remove_tags: Vec<String>,
+    rename: Option<String>,
+}
+
+impl Operations {
+    fn is_empty(&self) -> bool {
+        self.add_tags.is_empty() && self.remove_tags.is_empty() && self.rename.is_none()
+    }
+}
+
+struct Options {
+    selector: Selector,
+    operations: Operations,
+    assume_yes: bool,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut options = Options {
+            selector: Selector::default(),
+            operations: Operations::default(),
+            assume_yes: false,
+        };
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--network" => options.selector.networks.push(value()?),
+                "--name" => options.selector.name_pattern = Some(value()?),
+                "--tag" => options.selector.tags.push(value()?),
+                "--external-id-prefix" => options.selector.external_id_prefix = Some(value()?),
+                "--add-tag" => options.operations.add_tags.push(value()?),
+                "--remove-tag" => options.operations.remove_tags.push(value()?),
+                "--rename" => options.operations.rename = Some(value()?),
+                "--yes" => options.assume_yes = true,
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+
+        if options.selector.is_empty() {
+            return Err(
+                "At least one selector (--network, --name, --tag, --external-id-prefix) is required"
+                    .to_string(),
+            );
+        }
+        if options.operations.is_empty() {
+            return Err(
+                "At least one operation (--add-tag, --remove-tag, --rename) is required"
+                    .to_string(),
+            );
+        }
+
+        Ok(options)
+    }
+}
+
+struct WalletInfo {
+    id: String,
+    name: String,
+    network: String,
+    external_id: Option<String>,
+    tags: Vec<String>,
+}
+
+struct PlannedChange<'a> {
+    wallet: &'a WalletInfo,
+    add_tags: Vec<String>,
+    remove_tags: Vec<String>,
+    new_name: Option<String>,
+}
+
+impl PlannedChange<'_> {
+    fn is_noop(&self) -> bool {
+        self.add_tags.is_empty() && self.remove_tags.is_empty() && self.new_name.is_none()
+    }
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn render_name(template: &str, wallet: &WalletInfo, index: usize) -> String {
+    template
+        .replace("{name}", &wallet.name)
+        .replace("{network}", &wallet.network.to_lowercase())
+        .replace("{id}", &wallet.id)
+        .replace("{external_id}", wallet.external_id.as_deref().unwrap_or(""))
+        .replace("{index}", &(index + 1).to_string())
+}
+
+fn plan<'a>(wallets: &[&'a WalletInfo], operations: &Operations) -> Vec<PlannedChange<'a>> {
+    wallets
+        .iter()
+        .enumerate()
+        .map(|(index, wallet)| {
+            let new_name = operations
+                .rename
+                .as_deref()
+                .map(|template| render_name(template, wallet, index))
+                .filter(|name| *name != wallet.name);
+
+            PlannedChange {
+                wallet,
+                add_tags: operations
+                    .add_tags
+                    .iter()
+                    .filter(|tag| !wallet.tags.contains(tag))
+                    .cloned()
+                    .collect(),
+                remove_tags: operations
+                    .remove_tags
+                    .iter()
+                    .filter(|tag| wallet.tags.contains(tag))
+                    .cloned()
+                    .collect(),
+                new_name,
+            }
+        })
+        .filter(|change| !change.is_noop())
+        .collect()
+}
+
+fn confirm(prompt: &str) -> bool {
+    print!("{} [y/N] ", prompt);
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+async fn list_all_wallets(client: &DfnsApiClient) -> Result<Vec<WalletInfo>, DfnsError> {
+    let mut wallets = Vec::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListWalletsRequest {
+            query: Some(ListWalletsRequestQuery {
+                limit: Some("100".to_string()),
+                pagination_token: pagination_token.take(),
+                owner_id: None,
+                owner_username: None,
+            }),
+        };
+
+        let response = client.wallets().list_wallets(Some(request)).await?;
+        wallets.extend(response.items.into_iter().map(|wallet| WalletInfo {
+            id: wallet.id,
+            name: wallet.name.unwrap_or_default(),
+            network: format!("{:?}", wallet.network),
+            external_id: wallet.external_id,
+            tags: wallet.tags,
+        }));
+
+        match response.next_page_token {
+            Some(token) => pagination_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(wallets)
+}
+
+async fn apply(client: &DfnsApiClient, change: &PlannedChange<'_>) -> Result<(), DfnsError> {
+    let wallet_id = &change.wallet.id;
+
+    if let Some(name) = &change.new_name {
+        client
+            .wallets()
+            .update_wallet(UpdateWalletRequest {
+                wallet_id: wallet_id.clone(),
+                body: UpdateWalletRequestBody {
+                    name: Some(name.clone()),
+                    external_id: change.wallet.external_id.clone(),
+                },
+            })
+            .await?;
+    }
+
+    if !change.add_tags.is_empty() {
+        client
+            .wallets()
+            .tag_wallet(TagWalletRequest {
+                wallet_id: wallet_id.clone(),
+                body: TagWalletRequestBody {
+                    tags: change.add_tags.clone(),
+                },
+            })
+            .await?;
+    }
+
+    if !change.remove_tags.is_empty() {
+        client
+            .wallets()
+            .untag_wallet(UntagWalletRequest {
+                wallet_id: wallet_id.clone(),
+                body: UntagWalletRequestBody {
+                    tags: change.remove_tags.clone(),
+                },
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: bulk_update_wallets [--network N]... [--name PATTERN] [--tag T]... \
+                 [--external-id-prefix P] [--add-tag T]... [--remove-tag T]... \
+                 [--rename TEMPLATE] [--yes]"
+            );
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let wallets = match list_all_wallets(&client).await {
+        Ok(wallets) => wallets,
+        Err(e) => {
+            eprintln!("Error listing wallets: {:?}", e);
+            return;
+        }
+    };
+
+    let selected: Vec<&WalletInfo> = wallets
+        .iter()
+        .filter(|wallet| options.selector.matches(wallet))
+        .collect();
+    let changes = plan(&selected, &options.operations);
+
+    println!(
+        "{} of {} wallets selected, {} need changes:",
+        selected.len(),
+        wallets.len(),
+        changes.len()
+    );
+    for change in &changes {
+        println!(
+            "\n  {} {} ({})",
+            change.wallet.id, change.wallet.name, change.wallet.network
+        );
+        if let Some(name) = &change.new_name {
+            println!("    rename: {} -> {}", change.wallet.name, name);
+        }
+        if !change.add_tags.is_empty() {
+            println!("    tag:    {}", change.add_tags.join(", "));
+        }
+        if !change.remove_tags.is_empty() {
+            println!("    untag:  {}", change.remove_tags.join(", "));
+        }
+    }
+
+    if changes.is_empty() {
+        println!("Nothing to do.");
+        return;
+    }
+
+    if !options.assume_yes && !confirm(&format!("\nApply changes to {} wallets?", changes.len())) {
+        println!("Aborted.");
+        return;
+    }
+
+    let mut failures = 0;
+    for change in &changes {
+        match apply(&client, change).await {
+            Ok(()) => println!("Updated wallet {}", change.wallet.id),
+            Err(e) => {
+                failures += 1;
+                eprintln!("Error updating wallet {}: {:?}", change.wallet.id, e);
+            }
+        }
+    }
+
+    println!(
+        "\nDone: {} updated, {} failed",
+        changes.len() - failures,
+        failures
+    );
+}
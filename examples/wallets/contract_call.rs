@@ -0,0 +1,323 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+
+use abi::{Function, Token};
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        networks::types::{GetFeesQueryNetwork, ReadContractBodyNetwork},
+        wallets::types::GetWalletRequest,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use evm::{EvmTransaction, FeeSpeed};
+use ruint::aliases::U256;
+use serde_json::Value;
+use std::{env, fs, sync::Arc, time::Duration};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    command: String,
+    wallet_id: String,
+    positional: Vec<String>,
+    constructor: Option<String>,
+    value: U256,
+    speed: FeeSpeed,
+    gas_limit: Option<u64>,
+    nonce: Option<u64>,
+    wait: bool,
+    timeout: Duration,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            command: args.next().unwrap_or_else(|| "call".to_string()),
+            wallet_id: args
+                .next()
+                .unwrap_or_else(|| "example-wallet-id".to_string()),
+            positional: Vec::new(),
+            constructor: None,
+            value: U256::ZERO,
+            speed: FeeSpeed::Standard,
+            gas_limit: None,
+            nonce: None,
+            wait: true,
+            timeout: DEFAULT_TIMEOUT,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--constructor" => options.constructor = Some(value()?),
+                "--value" => options.value = abi::parse_uint(&value()?)?,
+                "--speed" => options.speed = FeeSpeed::parse(&value()?)?,
+                "--gas-limit" => {
+                    options.gas_limit = Some(value()?.parse().map_err(|_| "invalid --gas-limit")?)
+                }
+                "--nonce" => options.nonce = Some(value()?.parse().map_err(|_| "invalid --nonce")?),
+                "--timeout" => {
+                    options.timeout =
+                        Duration::from_secs(value()?.parse().map_err(|_| "invalid --timeout")?)
+                }
+                "--no-wait" => options.wait = false,
+                _ => options.positional.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct Artifact {
+    bytecode: Vec<u8>,
+    abi: Option<Value>,
+}
+
+fn load_artifact(source: &str) -> Result<Artifact, String> {
+    let content = match fs::read_to_string(source) {
+        Ok(content) => content,
+        Err(e) => {
+            let hex = source.trim_start_matches("0x");
+            if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("failed to read {}: {}", source, e));
+            }
+            source.to_string()
+        }
+    };
+    let content = content.trim();
+
+    if content.starts_with('{') {
+        let json: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let bytecode = json["bytecode"]["object"]
+            .as_str()
+            .or_else(|| json["bytecode"].as_str())
+            .ok_or("artifact has no bytecode")?;
+        return Ok(Artifact {
+            bytecode: hex::decode(bytecode.trim_start_matches("0x")).map_err(|e| e.to_string())?,
+            abi: Some(json["abi"].clone()).filter(Value::is_array),
+        });
+    }
+
+    Ok(Artifact {
+        bytecode: hex::decode(content.trim_start_matches("0x"))
+            .map_err(|e| format!("invalid bytecode: {}", e))?,
+        abi: None,
+    })
+}
+
+fn deploy_transaction(options: &Options) -> Result<EvmTransaction, String> {
+    let (source, args) = options
+        .positional
+        .split_first()
+        .ok_or("usage: deploy <wallet> <bytecode|artifact.json> [constructor args...]")?;
+    let artifact = load_artifact(source)?;
+
+    let constructor = match (&options.constructor, &artifact.abi) {
+        (Some(signature), _) => Some(Function::parse(signature)?),
+        (None, Some(abi)) => Function::from_json_abi(abi, "constructor").ok(),
+        (None, None) => None,
+    };
+
+    let mut data = artifact.bytecode;
+    match constructor {
+        Some(constructor) => data.extend(abi::encode(&constructor.parse_args(args)?)),
+        None if !args.is_empty() => {
+            return Err("constructor arguments given without --constructor or ABI".to_string());
+        }
+        None => {}
+    }
+
+    Ok(EvmTransaction {
+        to: None,
+        data,
+        value: options.value,
+        gas_limit: options.gas_limit,
+        nonce: options.nonce,
+        ..Default::default()
+    })
+}
+
+async fn call_transaction(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    options: &Options,
+) -> Result<EvmTransaction, String> {
+    let [contract, signature, args @ ..] = options.positional.as_slice() else {
+        return Err("usage: call <wallet> <contract> <signature> [args...]".to_string());
+    };
+    let function = Function::parse(signature)?;
+    let data = function.encode_args(args)?;
+
+    match evm::call_raw(client, network, contract, &data).await {
+        Ok(result) if !function.outputs.is_empty() => match function.decode_output(&result) {
+            Ok(tokens) => println!(
+                "Simulated result: {}",
+                Value::Array(tokens.iter().map(Token::to_json).collect())
+            ),
+            Err(e) => println!("Simulated call returned undecodable data: {}", e),
+        },
+        Ok(_) => println!("Simulated call succeeded"),
+        Err(e) => println!(
+            "Warning: simulated call failed (simulation runs without sender or value): {}",
+            e
+        ),
+    }
+
+    Ok(EvmTransaction {
+        to: Some(contract.clone()),
+        data,
+        value: options.value,
+        gas_limit: options.gas_limit,
+        nonce: options.nonce,
+        ..Default::default()
+    })
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let wallet = client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: options.wallet_id.clone(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let sender = wallet.address.ok_or("wallet has no address")?;
+    let read_network: ReadContractBodyNetwork = evm::convert_network(&wallet.network)?;
+    let fee_network: GetFeesQueryNetwork = evm::convert_network(&wallet.network)?;
+
+    let mut transaction = match options.command.as_str() {
+        "deploy" => deploy_transaction(options)?,
+        "call" => call_transaction(client, &read_network, options).await?,
+        other => {
+            return Err(format!(
+                "unknown command {} (expected deploy or call)",
+                other
+            ));
+        }
+    };
+
+    let create = transaction.to.is_none();
+    let intrinsic = evm::intrinsic_gas(&transaction.data, create);
+    match transaction.gas_limit {
+        Some(gas_limit) if gas_limit < intrinsic => {
+            return Err(format!(
+                "gas limit {} is below the intrinsic gas {}",
+                gas_limit, intrinsic
+            ));
+        }
+        Some(gas_limit) => println!("Intrinsic gas: {} (gas limit: {})", intrinsic, gas_limit),
+        None => println!(
+            "Intrinsic gas: {} (gas limit: estimated by Dfns)",
+            intrinsic
+        ),
+    }
+
+    let fees = evm::suggest_fees(client, fee_network, options.speed).await?;
+    println!(
+        "Fees ({:?}): max {} wei, priority {} wei",
+        options.speed, fees.max_fee_per_gas, fees.max_priority_fee_per_gas
+    );
+    if let (Ok(max_fee), Some(gas_limit)) = (
+        U256::from_str_radix(&fees.max_fee_per_gas, 10),
+        transaction.gas_limit,
+    ) {
+        println!(
+            "Max transaction fee: {} wei",
+            max_fee * U256::from(gas_limit)
+        );
+    }
+    transaction.fees = Some(fees);
+
+    if let (true, Some(nonce)) = (create, transaction.nonce) {
+        let address = evm::contract_address(&abi::parse_address(&sender)?, nonce);
+        println!("Contract address: {}", abi::checksum_address(&address));
+    }
+
+    let transaction_id = evm::broadcast(client, &options.wallet_id, &transaction).await?;
+    println!("Broadcast transaction {}", transaction_id);
+
+    if !options.wait {
+        return Ok(());
+    }
+
+    println!("Waiting for confirmation...");
+    let status =
+        evm::wait_for_transaction(client, &options.wallet_id, &transaction_id, options.timeout)
+            .await?;
+    println!("Status: {}", status.status);
+    if let Some(tx_hash) = &status.tx_hash {
+        println!("Transaction hash: {}", tx_hash);
+    }
+    if !status.is_confirmed() {
+        return Err(format!("transaction ended as {}", status.status));
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
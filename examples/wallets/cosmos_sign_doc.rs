@@ -0,0 +1,281 @@
+#[path = "../common/cosmos.rs"]
+mod cosmos;
+
+use async_trait::async_trait;
+use base64::Engine;
+use cosmos::{Coin, Fee, IbcTransfer, SignDoc, SignDocParams};
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::{
+        GenerateSignatureBody, GenerateSignatureBodyKind, GenerateSignatureRequest,
+        GetWalletRequest,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use std::{
+    env,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const IBC_TIMEOUT: Duration = Duration::from_secs(600);
+const NATIVE_DENOMS: &[(&str, &str)] = &[
+    ("cosmoshub-", "uatom"),
+    ("theta-testnet-", "uatom"),
+    ("osmosis-", "uosmo"),
+    ("osmo-test-", "uosmo"),
+    ("noble-", "uusdc"),
+    ("dydx-mainnet-", "adydx"),
+];
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    action: String,
+    wallet_id: String,
+    chain_id: String,
+    account_number: u64,
+    sequence: u64,
+    to: String,
+    amount: String,
+    denom: String,
+    fee_denom: Option<String>,
+    channel: String,
+    fee_amount: String,
+    gas_limit: u64,
+    memo: String,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            action: args.next().unwrap_or_else(|| "send".to_string()),
+            wallet_id: "example-wallet-id".to_string(),
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 0,
+            sequence: 0,
+            to: "cosmos1example".to_string(),
+            amount: "1000000".to_string(),
+            denom: "uatom".to_string(),
+            fee_denom: None,
+            channel: "channel-0".to_string(),
+            fee_amount: "5000".to_string(),
+            gas_limit: 200_000,
+            memo: String::new(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--wallet" => options.wallet_id = value()?,
+                "--chain-id" => options.chain_id = value()?,
+                "--account-number" => {
+                    options.account_number = value()?.parse().map_err(|_| "invalid number")?
+                }
+                "--sequence" => {
+                    options.sequence = value()?.parse().map_err(|_| "invalid number")?
+                }
+                "--to" | "--validator" | "--receiver" => options.to = value()?,
+                "--amount" => options.amount = value()?,
+                "--denom" => options.denom = value()?,
+                "--fee-denom" => options.fee_denom = Some(value()?),
+                "--channel" => options.channel = value()?,
+                "--fee" => options.fee_amount = value()?,
+                "--gas" => options.gas_limit = value()?.parse().map_err(|_| "invalid number")?,
+                "--memo" => options.memo = value()?,
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn fee_denom(&self) -> Result<&str, String> {
+        if let Some(denom) = &self.fee_denom {
+            return Ok(denom);
+        }
+        NATIVE_DENOMS
+            .iter()
+            .find(|(prefix, _)| self.chain_id.starts_with(prefix))
+            .map(|(_, denom)| *denom)
+            .ok_or_else(|| {
+                format!(
+                    "no native fee denom known for {}, pass --fee-denom",
+                    self.chain_id
+                )
+            })
+    }
+}
+
+fn build_message(options: &Options, sender: &str) -> Result<cosmos::Any, String> {
+    let amount = Coin::new(&options.amount, &options.denom);
+
+    match options.action.as_str() {
+        "send" => Ok(cosmos::msg_send(sender, &options.to, &[amount])),
+        "delegate" => Ok(cosmos::msg_delegate(sender, &options.to, &amount)),
+        "undelegate" => Ok(cosmos::msg_undelegate(sender, &options.to, &amount)),
+        "ibc-transfer" => {
+            let timeout = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| e.to_string())?
+                + IBC_TIMEOUT;
+            Ok(cosmos::msg_ibc_transfer(&IbcTransfer {
+                source_port: "transfer",
+                source_channel: &options.channel,
+                token: amount,
+                sender,
+                receiver: &options.to,
+                timeout_revision_number: 0,
+                timeout_revision_height: 0,
+                timeout_timestamp_nanos: timeout.as_nanos() as u64,
+                memo: "",
+            }))
+        }
+        other => Err(format!(
+            "unknown action {} (expected send, delegate, undelegate or ibc-transfer)",
+            other
+        )),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let wallet = match client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: options.wallet_id.clone(),
+        })
+        .await
+    {
+        Ok(wallet) => wallet,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            return;
+        }
+    };
+
+    let sign_doc = wallet
+        .address
+        .ok_or_else(|| "wallet has no address".to_string())
+        .and_then(|sender| build_message(&options, &sender))
+        .and_then(|message| {
+            let public_key = hex::decode(wallet.signing_key.public_key.trim_start_matches("0x"))
+                .map_err(|e| e.to_string())?;
+            Ok(SignDoc::new(&SignDocParams {
+                messages: &[message],
+                memo: &options.memo,
+                public_key: &public_key,
+                sequence: options.sequence,
+                fee: &Fee {
+                    amount: vec![Coin::new(&options.fee_amount, options.fee_denom()?)],
+                    gas_limit: options.gas_limit,
+                },
+                chain_id: &options.chain_id,
+                account_number: options.account_number,
+            }))
+        });
+
+    let sign_doc = match sign_doc {
+        Ok(sign_doc) => sign_doc,
+        Err(e) => {
+            eprintln!("Error building SignDoc: {}", e);
+            return;
+        }
+    };
+
+    println!("SignDoc: {}", sign_doc.to_hex());
+
+    let request = GenerateSignatureRequest {
+        wallet_id: options.wallet_id,
+        body: GenerateSignatureBody {
+            kind: GenerateSignatureBodyKind::SignDocDirect,
+            message: None,
+            external_id: None,
+            sign_doc: Some(sign_doc.to_hex()),
+            hash: None,
+            taproot_merkle_root: None,
+            transaction: None,
+            domain: None,
+            types: None,
+            psbt: None,
+            format: None,
+        },
+    };
+
+    let response = match client.wallets().generate_signature(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            return;
+        }
+    };
+
+    let Some(signature) = response.signature else {
+        println!("Signature pending: {:?}", response.status);
+        return;
+    };
+
+    match cosmos::signature_from_rs(&signature.r, &signature.s) {
+        Ok(signature) => {
+            let tx_raw = sign_doc.into_tx_raw(&signature);
+            println!(
+                "TxRaw (base64): {}",
+                base64::engine::general_purpose::STANDARD.encode(tx_raw)
+            );
+        }
+        Err(e) => eprintln!("Error decoding signature: {}", e),
+    }
+}
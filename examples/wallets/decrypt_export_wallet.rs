@@ -0,0 +1,239 @@
+#[path = "../common/key_shares.rs"]
+mod key_shares;
+
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce, aead::Aead};
+use dfns_sdk_rs::api::wallets::types::{
+    Curve, ExportWalletResponse, ExportWalletResponseEncryptedKeyShare, Protocol,
+};
+use k256::SecretKey;
+use rand::{RngCore, rngs::OsRng};
+use serde::Serialize;
+use std::{env, fs};
+
+const PASSPHRASE_ENV: &str = "DFNS_KEYSTORE_PASSPHRASE";
+const DECRYPTION_KEY_ENV: &str = "DFNS_EXPORT_DECRYPTION_KEY";
+const DEFAULT_ENVELOPE_PATH: &str = "demo-export.json";
+const DEFAULT_KEYSTORE_PATH: &str = "exported-wallet.keystore.json";
+const DEMO_SIGNERS: usize = 3;
+const DEMO_MIN_SIGNERS: usize = 2;
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+#[derive(Serialize)]
+struct Keystore {
+    version: u32,
+    public_key: String,
+    kdf: &'static str,
+    kdf_params: KdfParams,
+    cipher: &'static str,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Serialize)]
+struct KdfParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+fn reconstruct_private_key(
+    ephemeral: &SecretKey,
+    encrypted_shares: &[String],
+    min_signers: usize,
+    expected_public_key: &str,
+) -> Result<SecretKey, String> {
+    let shares = encrypted_shares
+        .iter()
+        .map(|encrypted| {
+            let payload = hex::decode(encrypted.trim_start_matches("0x"))
+                .map_err(|e| format!("invalid key share encoding: {}", e))?;
+            key_shares::KeyShare::from_bytes(&key_shares::decrypt(ephemeral, &payload)?)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    if shares.len() < min_signers {
+        return Err(format!(
+            "received {} key shares but {} are required",
+            shares.len(),
+            min_signers
+        ));
+    }
+
+    let private_key = key_shares::reconstruct_secret(&shares[..min_signers])?;
+    let expected = key_shares::parse_public_key(expected_public_key)?;
+    if private_key.public_key() != expected {
+        return Err("reconstructed key does not match the wallet public key".to_string());
+    }
+
+    Ok(private_key)
+}
+
+fn seal_keystore(private_key: &SecretKey, passphrase: &str) -> Result<Keystore, String> {
+    let mut salt = [0u8; 32];
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let params =
+        scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, 32).map_err(|e| e.to_string())?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key).map_err(|e| e.to_string())?;
+
+    let ciphertext = Aes256Gcm::new_from_slice(&key)
+        .map_err(|e| e.to_string())?
+        .encrypt(Nonce::from_slice(&nonce), private_key.to_bytes().as_slice())
+        .map_err(|_| "keystore encryption failed".to_string())?;
+
+    Ok(Keystore {
+        version: 1,
+        public_key: key_shares::compressed_public_key(private_key),
+        kdf: "scrypt",
+        kdf_params: KdfParams {
+            log_n: SCRYPT_LOG_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt: hex::encode(salt),
+        },
+        cipher: "aes-256-gcm",
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn generate_envelope(path: &str) -> Result<SecretKey, String> {
+    let private_key = SecretKey::random(&mut OsRng);
+    let recipient = SecretKey::random(&mut OsRng);
+    let shares = key_shares::split_secret(&private_key, DEMO_MIN_SIGNERS, DEMO_SIGNERS)?;
+
+    let encrypted_key_shares = shares
+        .iter()
+        .map(|share| {
+            let encrypted = key_shares::encrypt(&recipient.public_key(), &share.to_bytes())?;
+            Ok(ExportWalletResponseEncryptedKeyShare {
+                encrypted_key_share: hex::encode(encrypted),
+                signer_id: format!("demo-signer-{}", share.index),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let envelope = ExportWalletResponse {
+        curve: Curve::Secp256K1,
+        encrypted_key_shares,
+        min_signers: DEMO_MIN_SIGNERS as f64,
+        protocol: Protocol::Cggmp21,
+        public_key: key_shares::compressed_public_key(&private_key),
+    };
+    let json = serde_json::to_string_pretty(&envelope).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("failed to write {}: {}", path, e))?;
+
+    Ok(recipient)
+}
+
+fn load_envelope(path: &str) -> Result<ExportWalletResponse, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn main() {
+    let (flags, args): (Vec<String>, Vec<String>) =
+        env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    if let Some(flag) = flags.iter().find(|flag| *flag != "--generate") {
+        eprintln!("Error: unknown argument {}", flag);
+        return;
+    }
+    eprintln!("Note: {}", key_shares::DEMO_FORMAT_NOTICE);
+
+    let mut args = args.into_iter();
+    let envelope_path = args
+        .next()
+        .unwrap_or_else(|| DEFAULT_ENVELOPE_PATH.to_string());
+
+    if !flags.is_empty() {
+        match generate_envelope(&envelope_path) {
+            Ok(recipient) => {
+                println!("Demo export envelope written to {}", envelope_path);
+                println!(
+                    "Decrypt it with {}={}",
+                    DECRYPTION_KEY_ENV,
+                    hex::encode(recipient.to_bytes())
+                );
+            }
+            Err(e) => eprintln!("Error generating envelope: {}", e),
+        }
+        return;
+    }
+
+    let keystore_path = args
+        .next()
+        .unwrap_or_else(|| DEFAULT_KEYSTORE_PATH.to_string());
+
+    let decryption_key = match env::var(DECRYPTION_KEY_ENV)
+        .map_err(|_| format!("set {} to the envelope decryption key", DECRYPTION_KEY_ENV))
+        .and_then(|key| key_shares::parse_secret_key(&key))
+    {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let passphrase = match env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) if !passphrase.is_empty() => passphrase,
+        _ => {
+            eprintln!("Error: set {} to protect the keystore file", PASSPHRASE_ENV);
+            return;
+        }
+    };
+
+    let envelope = match load_envelope(&envelope_path) {
+        Ok(envelope) => envelope,
+        Err(e) => {
+            eprintln!("Error loading envelope: {}", e);
+            return;
+        }
+    };
+
+    let encrypted_shares: Vec<String> = envelope
+        .encrypted_key_shares
+        .into_iter()
+        .map(|share| share.encrypted_key_share)
+        .collect();
+
+    let private_key = match reconstruct_private_key(
+        &decryption_key,
+        &encrypted_shares,
+        envelope.min_signers as usize,
+        &envelope.public_key,
+    ) {
+        Ok(private_key) => private_key,
+        Err(e) => {
+            eprintln!(
+                "Error reconstructing private key (shares must use the demo format): {}",
+                e
+            );
+            return;
+        }
+    };
+
+    println!("Reconstructed private key for {}", envelope.public_key);
+
+    let keystore = match seal_keystore(&private_key, &passphrase) {
+        Ok(keystore) => keystore,
+        Err(e) => {
+            eprintln!("Error creating keystore: {}", e);
+            return;
+        }
+    };
+
+    match serde_json::to_string_pretty(&keystore)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&keystore_path, json).map_err(|e| e.to_string()))
+    {
+        Ok(()) => println!("Keystore written to {}", keystore_path),
+        Err(e) => eprintln!("Error writing keystore: {}", e),
+    }
+}
@@ -0,0 +1,357 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/nonces.rs"]
+mod nonces;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{networks::types::GetFeesQueryNetwork, wallets::types::GetWalletRequest},
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use evm::{EvmTransaction, FeeSpeed};
+use futures::future;
+use nonces::NonceManager;
+use ruint::aliases::U256;
+use std::{env, sync::Arc, time::Duration};
+
+const DEFAULT_STATE: &str = "nonce_state.json";
+const DEFAULT_STUCK_AFTER: Duration = Duration::from_secs(600);
+const DEFAULT_BUMP_PERCENT: u64 = 20;
+const SYNC_PAGES: usize = 10;
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    command: String,
+    wallet_id: String,
+    positional: Vec<String>,
+    state: String,
+    confirmed_nonce: Option<u64>,
+    count: usize,
+    data: Vec<u8>,
+    bump_percent: u64,
+    stuck_after: Duration,
+    speed: FeeSpeed,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            command: args.next().unwrap_or_else(|| "status".to_string()),
+            wallet_id: args
+                .next()
+                .unwrap_or_else(|| "example-wallet-id".to_string()),
+            positional: Vec::new(),
+            state: DEFAULT_STATE.to_string(),
+            confirmed_nonce: None,
+            count: 1,
+            data: Vec::new(),
+            bump_percent: DEFAULT_BUMP_PERCENT,
+            stuck_after: DEFAULT_STUCK_AFTER,
+            speed: FeeSpeed::Standard,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--state" => options.state = value()?,
+                "--confirmed-nonce" => {
+                    options.confirmed_nonce =
+                        Some(value()?.parse().map_err(|_| "invalid --confirmed-nonce")?)
+                }
+                "--count" => options.count = value()?.parse().map_err(|_| "invalid --count")?,
+                "--data" => {
+                    options.data = hex::decode(value()?.trim_start_matches("0x"))
+                        .map_err(|e| format!("invalid --data: {}", e))?
+                }
+                "--bump" => {
+                    options.bump_percent = value()?.parse().map_err(|_| "invalid --bump")?
+                }
+                "--stuck-after" => {
+                    options.stuck_after =
+                        Duration::from_secs(value()?.parse().map_err(|_| "invalid --stuck-after")?)
+                }
+                "--speed" => options.speed = FeeSpeed::parse(&value()?)?,
+                _ => options.positional.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn print_status(manager: &NonceManager, options: &Options) {
+    let Some(wallet) = manager.wallet(&options.wallet_id) else {
+        println!("No nonce state for {}", options.wallet_id);
+        return;
+    };
+
+    println!("Wallet {}:", options.wallet_id);
+    match wallet.confirmed_nonce {
+        Some(nonce) => println!("  Highest confirmed nonce: {}", nonce),
+        None => println!("  Highest confirmed nonce: unknown (set with --confirmed-nonce)"),
+    }
+    println!("  Next nonce: {}", wallet.next_nonce());
+
+    let pending = wallet.pending();
+    println!("  Pending transactions: {}", pending.len());
+    for tx in &pending {
+        println!(
+            "    nonce {:>5}  {}  {}  max fee {}",
+            tx.nonce,
+            tx.id,
+            tx.status,
+            tx.max_fee_per_gas.as_deref().unwrap_or("auto")
+        );
+    }
+
+    if !wallet.untracked_nonces.is_empty() {
+        println!(
+            "  Pending outside this tool: nonces {:?}",
+            wallet.untracked_nonces
+        );
+    }
+    if !wallet.unknown_nonce_pending.is_empty() {
+        println!(
+            "  Pending with unknown nonce (blocks send and fill-gaps): {}",
+            wallet.unknown_nonce_pending.join(", ")
+        );
+    }
+
+    let gaps = wallet.gaps();
+    if !gaps.is_empty() {
+        println!("  Nonce gaps blocking later transactions: {:?}", gaps);
+    }
+
+    let stuck = wallet.stuck(options.stuck_after);
+    if !stuck.is_empty() {
+        println!(
+            "  Stuck for more than {}s (use speed-up or cancel):",
+            options.stuck_after.as_secs()
+        );
+        for tx in stuck {
+            println!("    nonce {:>5}  {}", tx.nonce, tx.id);
+        }
+    }
+}
+
+async fn broadcast_all(
+    client: &DfnsApiClient,
+    manager: &mut NonceManager,
+    wallet_id: &str,
+    transactions: Vec<EvmTransaction>,
+) {
+    let results = future::join_all(
+        transactions
+            .iter()
+            .map(|transaction| evm::broadcast(client, wallet_id, transaction)),
+    )
+    .await;
+
+    for (transaction, result) in transactions.iter().zip(results) {
+        let nonce = transaction.nonce.unwrap_or_default();
+        match result {
+            Ok(id) => {
+                println!("  nonce {:>5}  broadcast {}", nonce, id);
+                if let Err(e) = manager.record(wallet_id, &id, transaction) {
+                    eprintln!("  nonce {:>5}  not tracked: {}", nonce, e);
+                }
+            }
+            Err(e) => eprintln!("  nonce {:>5}  failed: {}", nonce, e),
+        }
+    }
+}
+
+async fn run(
+    client: &DfnsApiClient,
+    manager: &mut NonceManager,
+    options: &Options,
+) -> Result<(), String> {
+    let wallet = client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: options.wallet_id.clone(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let sender = wallet.address.ok_or("wallet has no address")?;
+    let fee_network: GetFeesQueryNetwork = evm::convert_network(&wallet.network)?;
+
+    if let Some(nonce) = options.confirmed_nonce {
+        manager.set_confirmed_nonce(&options.wallet_id, nonce);
+    }
+    manager.sync(client, &options.wallet_id, SYNC_PAGES).await?;
+
+    match options.command.as_str() {
+        "status" => print_status(manager, options),
+        "send" => {
+            let [to, value] = options.positional.as_slice() else {
+                return Err("usage: send <wallet> <to> <value-wei> [--count N]".to_string());
+            };
+            let value = abi::parse_uint(value)?;
+            let fees = evm::suggest_fees(client, fee_network, options.speed).await?;
+            let transactions = manager
+                .reserve(&options.wallet_id, options.count)?
+                .into_iter()
+                .map(|nonce| EvmTransaction {
+                    to: Some(to.clone()),
+                    data: options.data.clone(),
+                    value,
+                    nonce: Some(nonce),
+                    fees: Some(fees.clone()),
+                    ..Default::default()
+                })
+                .collect();
+
+            println!("Broadcasting {} transactions:", options.count);
+            broadcast_all(client, manager, &options.wallet_id, transactions).await;
+        }
+        "fill-gaps" => {
+            manager.confirmed_nonce(&options.wallet_id)?;
+            manager.ensure_known_nonces(&options.wallet_id)?;
+            let gaps = manager
+                .wallet(&options.wallet_id)
+                .map(|wallet| wallet.gaps())
+                .unwrap_or_default();
+            if gaps.is_empty() {
+                println!("No nonce gaps to fill");
+                return Ok(());
+            }
+
+            let fees = evm::suggest_fees(client, fee_network, options.speed).await?;
+            let transactions = gaps
+                .into_iter()
+                .map(|nonce| EvmTransaction {
+                    to: Some(sender.clone()),
+                    value: U256::ZERO,
+                    gas_limit: Some(21_000),
+                    nonce: Some(nonce),
+                    fees: Some(fees.clone()),
+                    ..Default::default()
+                })
+                .collect();
+
+            println!("Filling nonce gaps with empty self-transfers:");
+            broadcast_all(client, manager, &options.wallet_id, transactions).await;
+        }
+        "speed-up" | "cancel" => {
+            let [transaction_id] = options.positional.as_slice() else {
+                return Err(format!(
+                    "usage: {} <wallet> <transaction-id>",
+                    options.command
+                ));
+            };
+            let current = evm::suggest_fees(client, fee_network, FeeSpeed::Fast).await?;
+            let replacement = if options.command == "cancel" {
+                manager.cancel(
+                    &options.wallet_id,
+                    transaction_id,
+                    &sender,
+                    options.bump_percent,
+                    &current,
+                )?
+            } else {
+                manager.speed_up(
+                    &options.wallet_id,
+                    transaction_id,
+                    options.bump_percent,
+                    &current,
+                )?
+            };
+
+            let id = evm::broadcast(client, &options.wallet_id, &replacement).await?;
+            manager.record(&options.wallet_id, &id, &replacement)?;
+            manager.mark_replaced(&options.wallet_id, transaction_id, &id);
+            println!(
+                "Replaced {} with {} at nonce {} (max fee {:?})",
+                transaction_id,
+                id,
+                replacement.nonce.unwrap_or_default(),
+                replacement.fees.map(|fees| fees.max_fee_per_gas)
+            );
+        }
+        other => {
+            return Err(format!(
+                "unknown command {} (expected status, send, fill-gaps, speed-up or cancel)",
+                other
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let mut manager = match NonceManager::load(&options.state) {
+        Ok(manager) => manager,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &mut manager, &options).await {
+        eprintln!("Error: {}", e);
+    }
+
+    if let Err(e) = manager.save() {
+        eprintln!("Error saving nonce state: {}", e);
+    }
+}
@@ -0,0 +1,444 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/multicall.rs"]
+mod multicall;
+#[path = "../common/tokens.rs"]
+mod tokens;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        networks::types::ReadContractBodyNetwork,
+        wallets::types::{
+            AssetKind, GetWalletAssetsRequest, GetWalletAssetsRequestQuery, ListWalletsRequest,
+            ListWalletsRequestQuery,
+        },
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use futures::stream::{self, StreamExt};
+use ruint::aliases::U256;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fs,
+    sync::Arc,
+};
+use tokens::{MetadataCache, TokenClient};
+
+const DEFAULT_CONCURRENCY: usize = 8;
+const UNTAGGED: &str = "(untagged)";
+const UNKNOWN_SYMBOL: &str = "UNKNOWN";
+const DEFAULT_TOKEN_CACHE: &str = "token_metadata.json";
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    breakdown: bool,
+    concurrency: usize,
+    prices_file: Option<String>,
+    currency: String,
+    token_cache: String,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut options = Options {
+            breakdown: false,
+            concurrency: DEFAULT_CONCURRENCY,
+            prices_file: None,
+            currency: "USD".to_string(),
+            token_cache: DEFAULT_TOKEN_CACHE.to_string(),
+        };
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--breakdown" => options.breakdown = true,
+                "--concurrency" => {
+                    let concurrency = value()?;
+                    options.concurrency = concurrency
+                        .parse()
+                        .ok()
+                        .filter(|concurrency| *concurrency > 0)
+                        .ok_or_else(|| format!("invalid --concurrency: {}", concurrency))?;
+                }
+                "--prices" => options.prices_file = Some(value()?),
+                "--currency" => options.currency = value()?,
+                "--token-cache" => options.token_cache = value()?,
+                other => return Err(format!("unknown argument {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct WalletInfo {
+    id: String,
+    name: String,
+    network: String,
+    read_network: Option<ReadContractBodyNetwork>,
+    tags: Vec<String>,
+}
+
+struct Balance {
+    symbol: Option<String>,
+    contract: Option<String>,
+    amount: U256,
+    decimals: u8,
+}
+
+#[derive(Default)]
+struct Holding {
+    amount: U256,
+    decimals: u8,
+    value: Option<f64>,
+}
+
+impl Holding {
+    fn add(&mut self, amount: U256, decimals: u8, price: Option<f64>) {
+        if decimals > self.decimals {
+            self.amount = self.amount.saturating_mul(scale(decimals - self.decimals));
+            self.decimals = decimals;
+        }
+        self.amount = self
+            .amount
+            .saturating_add(amount.saturating_mul(scale(self.decimals - decimals)));
+        if let Some(price) = price {
+            *self.value.get_or_insert(0.0) += units(amount, decimals) * price;
+        }
+    }
+}
+
+fn scale(decimals: u8) -> U256 {
+    U256::from(10u8).saturating_pow(U256::from(decimals))
+}
+
+fn units(amount: U256, decimals: u8) -> f64 {
+    evm::format_units(amount, decimals)
+        .parse()
+        .unwrap_or_default()
+}
+
+type Holdings = BTreeMap<String, BTreeMap<String, Holding>>;
+
+fn load_prices(path: &str) -> Result<HashMap<String, f64>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let prices: HashMap<String, f64> =
+        serde_json::from_str(&contents).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(prices
+        .into_iter()
+        .map(|(symbol, price)| (symbol.to_uppercase(), price))
+        .collect())
+}
+
+async fn list_all_wallets(client: &DfnsApiClient) -> Result<Vec<WalletInfo>, DfnsError> {
+    let mut wallets = Vec::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListWalletsRequest {
+            query: Some(ListWalletsRequestQuery {
+                limit: Some("100".to_string()),
+                pagination_token: pagination_token.take(),
+                owner_id: None,
+                owner_username: None,
+            }),
+        };
+
+        let response = client.wallets().list_wallets(Some(request)).await?;
+        wallets.extend(response.items.into_iter().map(|wallet| {
+            WalletInfo {
+                id: wallet.id,
+                name: wallet.name.unwrap_or_default(),
+                network: format!("{:?}", wallet.network),
+                read_network: serde_json::to_value(&wallet.network)
+                    .and_then(serde_json::from_value)
+                    .ok(),
+                tags: wallet.tags,
+            }
+        }));
+
+        match response.next_page_token {
+            Some(token) => pagination_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(wallets)
+}
+
+async fn fetch_balances(
+    client: &DfnsApiClient,
+    wallet: &WalletInfo,
+) -> Result<Vec<Balance>, DfnsError> {
+    let request = GetWalletAssetsRequest {
+        wallet_id: wallet.id.clone(),
+        query: Some(GetWalletAssetsRequestQuery { net_worth: None }),
+    };
+
+    let response = client.wallets().get_wallet_assets(request).await?;
+    Ok(response
+        .assets
+        .into_iter()
+        .filter_map(|asset| {
+            let amount = U256::from_str_radix(&asset.balance, 10).ok()?;
+            (!amount.is_zero()).then(|| Balance {
+                symbol: asset.symbol.map(|symbol| symbol.to_uppercase()),
+                contract: asset.contract.filter(|_| asset.kind == AssetKind::Erc20),
+                amount,
+                decimals: asset.decimals as u8,
+            })
+        })
+        .collect())
+}
+
+async fn resolve_symbols(
+    client: &DfnsApiClient,
+    mut cache: MetadataCache,
+    results: &[(&WalletInfo, Result<Vec<Balance>, DfnsError>)],
+) -> MetadataCache {
+    let mut unlabeled: BTreeMap<String, (ReadContractBodyNetwork, Vec<String>)> = BTreeMap::new();
+    for (wallet, result) in results {
+        let (Some(network), Ok(balances)) = (&wallet.read_network, result) else {
+            continue;
+        };
+        let contracts = &mut unlabeled
+            .entry(wallet.network.clone())
+            .or_insert_with(|| (network.clone(), Vec::new()))
+            .1;
+        for contract in balances
+            .iter()
+            .filter(|balance| balance.symbol.is_none())
+            .filter_map(|balance| balance.contract.as_ref())
+        {
+            if !contracts.contains(contract) {
+                contracts.push(contract.clone());
+            }
+        }
+    }
+
+    for (name, (network, contracts)) in unlabeled {
+        if contracts.is_empty() {
+            continue;
+        }
+        let mut token_client = TokenClient::new(client, network, cache);
+        if let Err(e) = token_client.prefetch_metadata(&contracts).await {
+            eprintln!("Error reading token metadata on {}: {}", name, e);
+        }
+        cache = token_client.into_cache();
+    }
+
+    cache
+}
+
+fn balance_symbol(cache: &MetadataCache, wallet: &WalletInfo, balance: &Balance) -> String {
+    balance
+        .symbol
+        .clone()
+        .or_else(|| {
+            let metadata = cache.get(wallet.read_network.as_ref()?, balance.contract.as_ref()?)?;
+            metadata.symbol.as_ref().map(|symbol| symbol.to_uppercase())
+        })
+        .unwrap_or_else(|| UNKNOWN_SYMBOL.to_string())
+}
+
+fn add_holding(
+    holdings: &mut Holdings,
+    group: &str,
+    key: &str,
+    balance: &Balance,
+    price: Option<f64>,
+) {
+    holdings
+        .entry(group.to_string())
+        .or_default()
+        .entry(key.to_string())
+        .or_default()
+        .add(balance.amount, balance.decimals, price);
+}
+
+fn format_value(value: Option<f64>, currency: &str) -> String {
+    match value {
+        Some(value) => format!("{:.2} {}", value, currency),
+        None => "-".to_string(),
+    }
+}
+
+fn print_holdings(title: &str, holdings: &Holdings, currency: &str) {
+    println!("\n{}:", title);
+    for (group, assets) in holdings {
+        let total: Option<f64> = assets
+            .values()
+            .filter_map(|holding| holding.value)
+            .reduce(|a, b| a + b);
+        println!("  {} (total: {})", group, format_value(total, currency));
+        for (key, holding) in assets {
+            println!(
+                "    {:<16} {:>32}  {}",
+                key,
+                evm::format_units(holding.amount, holding.decimals),
+                format_value(holding.value, currency)
+            );
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let prices = match &options.prices_file {
+        Some(path) => match load_prices(path) {
+            Ok(prices) => prices,
+            Err(e) => {
+                eprintln!("Error loading prices: {}", e);
+                return;
+            }
+        },
+        None => HashMap::new(),
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let wallets = match list_all_wallets(&client).await {
+        Ok(wallets) => wallets,
+        Err(e) => {
+            eprintln!("Error listing wallets: {:?}", e);
+            return;
+        }
+    };
+
+    let client = &client;
+    let results: Vec<_> = stream::iter(&wallets)
+        .map(|wallet| async move { (wallet, fetch_balances(client, wallet).await) })
+        .buffer_unordered(options.concurrency)
+        .collect()
+        .await;
+
+    let cache = match MetadataCache::load(&options.token_cache) {
+        Ok(cache) => cache,
+        Err(e) => {
+            eprintln!("Error loading token metadata cache: {}", e);
+            MetadataCache::default()
+        }
+    };
+    let cache = resolve_symbols(client, cache, &results).await;
+
+    let mut by_network = Holdings::new();
+    let mut by_asset = Holdings::new();
+    let mut by_tag = Holdings::new();
+    let mut by_wallet = Holdings::new();
+    let mut grand_total: Option<f64> = None;
+    let mut failed = 0;
+
+    for (wallet, result) in results {
+        let balances = match result {
+            Ok(balances) => balances,
+            Err(e) => {
+                eprintln!("Error fetching assets for wallet {}: {:?}", wallet.id, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for balance in &balances {
+            let symbol = balance_symbol(&cache, wallet, balance);
+            let price = prices.get(&symbol).copied();
+            if let Some(price) = price {
+                *grand_total.get_or_insert(0.0) += units(balance.amount, balance.decimals) * price;
+            }
+
+            let symbol = symbol.as_str();
+            add_holding(&mut by_network, &wallet.network, symbol, balance, price);
+            add_holding(&mut by_asset, symbol, &wallet.network, balance, price);
+
+            if wallet.tags.is_empty() {
+                add_holding(&mut by_tag, UNTAGGED, symbol, balance, price);
+            }
+            for tag in &wallet.tags {
+                add_holding(&mut by_tag, tag, symbol, balance, price);
+            }
+
+            if options.breakdown {
+                let label = format!("{} {} ({})", wallet.id, wallet.name, wallet.network);
+                add_holding(&mut by_wallet, &label, symbol, balance, price);
+            }
+        }
+    }
+
+    if let Err(e) = cache.save() {
+        eprintln!("Error saving token metadata cache: {}", e);
+    }
+
+    println!("Portfolio across {} wallets", wallets.len() - failed);
+    if failed > 0 {
+        println!(
+            "Assets of {} wallets could not be fetched, totals are partial",
+            failed
+        );
+    }
+    print_holdings("By network", &by_network, &options.currency);
+    print_holdings("By asset", &by_asset, &options.currency);
+    print_holdings("By tag", &by_tag, &options.currency);
+    if options.breakdown {
+        print_holdings("By wallet", &by_wallet, &options.currency);
+    }
+
+    println!(
+        "\nTotal value: {}",
+        format_value(grand_total, &options.currency)
+    );
+}
@@ -0,0 +1,219 @@
+#[path = "../common/key_shares.rs"]
+mod key_shares;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::{
+        BodyEncryptedKeyShare, CreateWalletBodyNetwork, Curve, ImportWalletRequestBody, Protocol,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use std::{env, sync::Arc};
+
+const PRIVATE_KEY_ENV: &str = "DFNS_IMPORT_PRIVATE_KEY";
+const DEFAULT_MIN_SIGNERS: usize = 2;
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    network: CreateWalletBodyNetwork,
+    min_signers: usize,
+    cluster_id: Option<String>,
+    name: Option<String>,
+    external_id: Option<String>,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut options = Options {
+            network: CreateWalletBodyNetwork::Ethereum,
+            min_signers: DEFAULT_MIN_SIGNERS,
+            cluster_id: None,
+            name: None,
+            external_id: None,
+        };
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--network" => {
+                    let network = value()?;
+                    options.network =
+                        serde_json::from_value(serde_json::Value::String(network.clone()))
+                            .map_err(|_| format!("unsupported network: {}", network))?;
+                }
+                "--min-signers" => {
+                    let min_signers = value()?;
+                    options.min_signers = min_signers
+                        .parse()
+                        .map_err(|_| format!("invalid --min-signers: {}", min_signers))?
+                }
+                "--cluster" => options.cluster_id = Some(value()?),
+                "--name" => options.name = Some(value()?),
+                "--external-id" => options.external_id = Some(value()?),
+                other => return Err(format!("unknown argument {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct SignerKey {
+    signer_id: String,
+    encryption_key: String,
+}
+
+async fn signer_keys(
+    client: &DfnsApiClient,
+    cluster_id: Option<&str>,
+) -> Result<Vec<SignerKey>, String> {
+    let response = client
+        .signers()
+        .list_signers()
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+
+    let cluster = response
+        .clusters
+        .into_iter()
+        .find(|cluster| cluster_id.is_none_or(|id| cluster.cluster_id == id))
+        .ok_or_else(|| "no matching signer cluster found".to_string())?;
+
+    Ok(cluster
+        .signers
+        .into_iter()
+        .map(|signer| SignerKey {
+            signer_id: signer.signer_id,
+            encryption_key: signer.encryption_key,
+        })
+        .collect())
+}
+
+fn encrypt_shares(
+    private_key: &str,
+    signers: &[SignerKey],
+    min_signers: usize,
+) -> Result<Vec<BodyEncryptedKeyShare>, String> {
+    let secret = key_shares::parse_secret_key(private_key)?;
+    let shares = key_shares::split_secret(&secret, min_signers, signers.len())?;
+
+    println!(
+        "Split key with public key {} into {} shares (threshold {})",
+        key_shares::compressed_public_key(&secret),
+        shares.len(),
+        min_signers
+    );
+
+    signers
+        .iter()
+        .zip(&shares)
+        .map(|(signer, share)| {
+            let encryption_key = key_shares::parse_public_key(&signer.encryption_key)
+                .map_err(|e| format!("signer {}: {}", signer.signer_id, e))?;
+            let encrypted = key_shares::encrypt(&encryption_key, &share.to_bytes())?;
+            Ok(BodyEncryptedKeyShare {
+                encrypted_key_share: hex::encode(encrypted),
+                signer_id: signer.signer_id.clone(),
+            })
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    eprintln!("Note: {}", key_shares::DEMO_FORMAT_NOTICE);
+
+    let private_key = match env::var(PRIVATE_KEY_ENV) {
+        Ok(private_key) => private_key,
+        Err(_) => {
+            eprintln!("Error: set {} to the hex private key", PRIVATE_KEY_ENV);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let signers = match signer_keys(&client, options.cluster_id.as_deref()).await {
+        Ok(signers) => signers,
+        Err(e) => {
+            eprintln!("Error listing signers: {}", e);
+            return;
+        }
+    };
+
+    let encrypted_key_shares = match encrypt_shares(&private_key, &signers, options.min_signers) {
+        Ok(shares) => shares,
+        Err(e) => {
+            eprintln!("Error preparing key shares: {}", e);
+            return;
+        }
+    };
+
+    let body = ImportWalletRequestBody {
+        curve: Curve::Secp256K1,
+        network: options.network,
+        protocol: Protocol::Cggmp21,
+        min_signers: options.min_signers as f64,
+        name: options.name,
+        external_id: options.external_id,
+        encrypted_key_shares,
+    };
+
+    match serde_json::to_string_pretty(&body) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Error serializing request body: {}", e),
+    }
+}
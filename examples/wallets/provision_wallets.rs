@@ -0,0 +1,320 @@
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::{
+        CreateWalletBodyNetwork, CreateWalletRequest, CreateWalletRequestBody, ListWalletsRequest,
+        ListWalletsRequestQuery,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use serde::Deserialize;
+use std::{collections::HashMap, env, fs, sync::Arc};
+
+const DEFAULT_TEMPLATE: &str = "examples/wallets/provision_wallets.toml";
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Template {
+    customer: String,
+    name_pattern: String,
+    external_id_pattern: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    delay_delegation: Option<bool>,
+    delegate_to: Option<String>,
+    wallets: Vec<WalletTemplate>,
+}
+
+#[derive(Deserialize)]
+struct WalletTemplate {
+    network: String,
+    label: Option<String>,
+    name: Option<String>,
+    external_id: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    delay_delegation: Option<bool>,
+    delegate_to: Option<String>,
+}
+
+struct DesiredWallet {
+    network: CreateWalletBodyNetwork,
+    network_name: String,
+    name: String,
+    external_id: String,
+    tags: Vec<String>,
+    delay_delegation: Option<bool>,
+    delegate_to: Option<String>,
+}
+
+fn render(pattern: &str, customer: &str, network: &str, label: &str, index: usize) -> String {
+    let fields = [
+        ("customer", customer.to_string()),
+        ("network", network.to_string()),
+        ("network_lower", network.to_lowercase()),
+        ("label", label.to_string()),
+        ("index", index.to_string()),
+    ];
+
+    let mut text = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let field = rest[1..].find('}').and_then(|end| {
+            let key = &rest[1..end + 1];
+            fields
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| (end + 2, value))
+        });
+        match field {
+            Some((len, value)) => {
+                text.push_str(value);
+                rest = &rest[len..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+fn parse_network(network: &str) -> Result<CreateWalletBodyNetwork, String> {
+    serde_json::from_value(serde_json::Value::String(network.to_string()))
+        .map_err(|_| format!("unsupported network: {}", network))
+}
+
+fn load_template(path: &str) -> Result<Vec<DesiredWallet>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let template: Template = toml::from_str(&contents).map_err(|e| format!("{}: {}", path, e))?;
+
+    let mut per_network: HashMap<&str, usize> = HashMap::new();
+    let desired = template
+        .wallets
+        .iter()
+        .map(|wallet| {
+            let index = per_network.entry(wallet.network.as_str()).or_default();
+            *index += 1;
+            let index = *index;
+            let label = wallet
+                .label
+                .clone()
+                .unwrap_or_else(|| wallet.network.to_lowercase());
+            let name_pattern = wallet.name.as_deref().unwrap_or(&template.name_pattern);
+            let external_id_pattern = wallet
+                .external_id
+                .as_deref()
+                .unwrap_or(&template.external_id_pattern);
+            let mut tags = template.tags.clone();
+            for tag in &wallet.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+
+            Ok(DesiredWallet {
+                network: parse_network(&wallet.network)?,
+                network_name: wallet.network.clone(),
+                name: render(
+                    name_pattern,
+                    &template.customer,
+                    &wallet.network,
+                    &label,
+                    index,
+                ),
+                external_id: render(
+                    external_id_pattern,
+                    &template.customer,
+                    &wallet.network,
+                    &label,
+                    index,
+                ),
+                tags,
+                delay_delegation: wallet.delay_delegation.or(template.delay_delegation),
+                delegate_to: wallet
+                    .delegate_to
+                    .clone()
+                    .or_else(|| template.delegate_to.clone()),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut seen = HashMap::new();
+    for wallet in &desired {
+        if let Some(previous) = seen.insert(wallet.external_id.as_str(), wallet.name.as_str()) {
+            return Err(format!(
+                "{}: wallets {:?} and {:?} both render external id {}, \
+                 add {{label}} or {{index}} to external_id_pattern",
+                path, previous, wallet.name, wallet.external_id
+            ));
+        }
+    }
+
+    Ok(desired)
+}
+
+async fn existing_wallets_by_external_id(
+    client: &DfnsApiClient,
+) -> Result<HashMap<String, String>, DfnsError> {
+    let mut wallets = HashMap::new();
+    let mut pagination_token = None;
+
+    loop {
+        let request = ListWalletsRequest {
+            query: Some(ListWalletsRequestQuery {
+                limit: Some("100".to_string()),
+                pagination_token: pagination_token.take(),
+                owner_id: None,
+                owner_username: None,
+            }),
+        };
+
+        let response = client.wallets().list_wallets(Some(request)).await?;
+        wallets.extend(
+            response
+                .items
+                .into_iter()
+                .filter_map(|wallet| Some((wallet.external_id?, wallet.id))),
+        );
+
+        match response.next_page_token {
+            Some(token) => pagination_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(wallets)
+}
+
+#[tokio::main]
+async fn main() {
+    let mut template_path = DEFAULT_TEMPLATE.to_string();
+    let mut dry_run = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            path => template_path = path.to_string(),
+        }
+    }
+
+    let desired = match load_template(&template_path) {
+        Ok(desired) => desired,
+        Err(e) => {
+            eprintln!("Error loading template: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let existing = match existing_wallets_by_external_id(&client).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            eprintln!("Error listing wallets: {:?}", e);
+            return;
+        }
+    };
+
+    let (mut created, mut planned, mut skipped, mut failed) = (0, 0, 0, 0);
+    for wallet in desired {
+        if let Some(wallet_id) = existing.get(&wallet.external_id) {
+            println!(
+                "Exists:  {} ({}) -> {}",
+                wallet.external_id, wallet.network_name, wallet_id
+            );
+            skipped += 1;
+            continue;
+        }
+
+        if dry_run {
+            println!(
+                "Create:  {} ({}) name={:?} tags={:?}",
+                wallet.external_id, wallet.network_name, wallet.name, wallet.tags
+            );
+            planned += 1;
+            continue;
+        }
+
+        let request = CreateWalletRequest {
+            body: CreateWalletRequestBody {
+                network: wallet.network,
+                name: Some(wallet.name),
+                external_id: Some(wallet.external_id.clone()),
+                delay_delegation: wallet.delay_delegation,
+                delegate_to: wallet.delegate_to,
+                signing_key: None,
+                tags: Some(wallet.tags),
+            },
+        };
+
+        match client.wallets().create_wallet(request).await {
+            Ok(response) => {
+                println!(
+                    "Created: {} ({}) -> {}",
+                    wallet.external_id, wallet.network_name, response.id
+                );
+                created += 1;
+            }
+            Err(e) => {
+                eprintln!("Error creating wallet {}: {:?}", wallet.external_id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if dry_run {
+        println!(
+            "\nDry run: {} to create, {} already existed",
+            planned, skipped
+        );
+        return;
+    }
+    println!(
+        "\nProvisioning finished: {} created, {} already existed, {} failed",
+        created, skipped, failed
+    );
+}
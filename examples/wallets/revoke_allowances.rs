@@ -0,0 +1,492 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/multicall.rs"]
+mod multicall;
+#[path = "../common/tokens.rs"]
+mod tokens;
+
+use abi::{Function, Token};
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        networks::types::ReadContractBodyNetwork,
+        wallets::types::{
+            BroadcastTransactionBody, BroadcastTransactionBodyKind, BroadcastTransactionRequest,
+            GetWalletHistoryQueryKind, GetWalletHistoryRequest, GetWalletHistoryRequestQuery,
+            GetWalletRequest, ListTransactionsRequest, ListTransactionsRequestQuery,
+        },
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use multicall::Batch;
+use ruint::aliases::U256;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    io::{self, Write},
+    sync::Arc,
+};
+use tokens::{MetadataCache, TokenClient};
+
+const APPROVE: &str = "approve(address,uint256)";
+const ALLOWANCE: &str = "allowance(address,address)(uint256)";
+const DEFAULT_MAX_PAGES: usize = 20;
+const KNOWN_SPENDERS: &[(&str, &str)] = &[
+    (
+        "0x000000000022D473030F116dDEE9F6B43aC78BA3",
+        "Uniswap Permit2",
+    ),
+    (
+        "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D",
+        "Uniswap V2 Router",
+    ),
+    (
+        "0xE592427A0AEce92De3Edee1F18E0157C05861564",
+        "Uniswap V3 SwapRouter",
+    ),
+    (
+        "0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45",
+        "Uniswap SwapRouter02",
+    ),
+    (
+        "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD",
+        "Uniswap Universal Router",
+    ),
+    (
+        "0x1111111254EEB25477B68fb85Ed929f73A960582",
+        "1inch Router v5",
+    ),
+    (
+        "0x111111125421cA6dc452d289314280a0f8842A65",
+        "1inch Router v6",
+    ),
+    (
+        "0xDef1C0ded9bec7F1a1670819833240f027b25EfF",
+        "0x Exchange Proxy",
+    ),
+];
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    wallet_id: String,
+    spenders: Vec<String>,
+    labels_file: Option<String>,
+    max_pages: usize,
+    revoke: Option<String>,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            wallet_id: "example-wallet-id".to_string(),
+            spenders: Vec::new(),
+            labels_file: None,
+            max_pages: DEFAULT_MAX_PAGES,
+            revoke: None,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--spender" => options.spenders.push(value()?),
+                "--labels" => options.labels_file = Some(value()?),
+                "--max-pages" => {
+                    options.max_pages = value()?.parse().map_err(|_| "invalid --max-pages")?
+                }
+                "--revoke" => options.revoke = Some(value()?),
+                other if other.starts_with("--") => {
+                    return Err(format!("unknown argument {}", other));
+                }
+                _ => options.wallet_id = arg,
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct Allowance {
+    token: String,
+    spender: String,
+    amount: U256,
+}
+
+fn load_labels(path: Option<&str>) -> Result<BTreeMap<String, String>, String> {
+    let mut labels: BTreeMap<String, String> = KNOWN_SPENDERS
+        .iter()
+        .map(|(address, label)| (address.to_lowercase(), label.to_string()))
+        .collect();
+
+    if let Some(path) = path {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+        let custom: BTreeMap<String, String> = serde_json::from_str(&content)
+            .map_err(|e| format!("failed to parse {}: {}", path, e))?;
+        for (address, label) in custom {
+            if !is_address(&address) {
+                return Err(format!("{}: {} is not an address", path, address));
+            }
+            labels.insert(address.to_lowercase(), label);
+        }
+    }
+
+    Ok(labels)
+}
+
+fn is_address(value: &str) -> bool {
+    abi::parse_address(value).is_ok()
+}
+
+async fn scan_history(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    max_pages: usize,
+    tokens: &mut BTreeSet<String>,
+) -> Result<(), String> {
+    let mut pagination_token = None;
+    for _ in 0..max_pages {
+        let request = GetWalletHistoryRequest {
+            wallet_id: wallet_id.to_string(),
+            query: Some(GetWalletHistoryRequestQuery {
+                direction: None,
+                kind: Some(GetWalletHistoryQueryKind::Erc20Transfer),
+                limit: Some("100".to_string()),
+                contract: None,
+                pagination_token: pagination_token.take(),
+            }),
+        };
+
+        let response = client
+            .wallets()
+            .get_wallet_history(request)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let page = serde_json::to_value(&response).map_err(|e| e.to_string())?;
+
+        for event in page["items"].as_array().into_iter().flatten() {
+            if let Some(contract) = event["contract"].as_str().filter(|c| is_address(c)) {
+                tokens.insert(contract.to_lowercase());
+            }
+        }
+
+        match page["nextPageToken"].as_str() {
+            Some(token) => pagination_token = Some(token.to_string()),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
+async fn scan_approve_transactions(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    max_pages: usize,
+    tokens: &mut BTreeSet<String>,
+    spenders: &mut BTreeSet<String>,
+) -> Result<(), String> {
+    let selector = format!("0x{}", hex::encode(Function::parse(APPROVE)?.selector()));
+    let mut pagination_token = None;
+    for _ in 0..max_pages {
+        let request = ListTransactionsRequest {
+            wallet_id: wallet_id.to_string(),
+            query: Some(ListTransactionsRequestQuery {
+                limit: Some("100".to_string()),
+                pagination_token: pagination_token.take(),
+            }),
+        };
+
+        let response = client
+            .wallets()
+            .list_transactions(request)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let page = serde_json::to_value(&response).map_err(|e| e.to_string())?;
+
+        for transaction in page["items"].as_array().into_iter().flatten() {
+            let body = &transaction["requestBody"];
+            let (Some(to), Some(data)) = (body["to"].as_str(), body["data"].as_str()) else {
+                continue;
+            };
+            if !data.to_lowercase().starts_with(&selector) || data.len() < 10 + 64 {
+                continue;
+            }
+            let spender = format!("0x{}", &data[10 + 24..10 + 64]);
+            if is_address(to) && is_address(&spender) {
+                tokens.insert(to.to_lowercase());
+                spenders.insert(spender.to_lowercase());
+            }
+        }
+
+        match page["nextPageToken"].as_str() {
+            Some(token) => pagination_token = Some(token.to_string()),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
+async fn current_allowances(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    owner: &str,
+    tokens: &BTreeSet<String>,
+    spenders: &BTreeSet<String>,
+) -> Result<Vec<Allowance>, String> {
+    let function = Function::parse(ALLOWANCE)?;
+    let owner = Token::Address(abi::parse_address(owner)?);
+    let mut batch = Batch::new();
+    let mut pairs = Vec::new();
+    for token in tokens {
+        for spender in spenders {
+            let spender_token = Token::Address(abi::parse_address(spender)?);
+            batch.add(token, &function, &[owner.clone(), spender_token])?;
+            pairs.push((token.clone(), spender.clone()));
+        }
+    }
+
+    let results = batch.execute(client, network).await?;
+    Ok(pairs
+        .into_iter()
+        .zip(results)
+        .filter_map(|((token, spender), result)| {
+            let amount = result.ok()?.into_iter().next()?.into_uint()?;
+            (amount > U256::ZERO).then_some(Allowance {
+                token,
+                spender,
+                amount,
+            })
+        })
+        .collect())
+}
+
+fn select(allowances: &[Allowance], revoke: Option<&str>) -> Result<Vec<usize>, String> {
+    let answer = match revoke {
+        Some(answer) => answer.to_string(),
+        None => {
+            print!("Revoke which allowances? (e.g. 1,3 or all, empty to skip): ");
+            io::stdout().flush().map_err(|e| e.to_string())?;
+            let mut answer = String::new();
+            io::stdin()
+                .read_line(&mut answer)
+                .map_err(|e| e.to_string())?;
+            answer
+        }
+    };
+
+    let answer = answer.trim();
+    if answer.is_empty() || answer == "none" {
+        return Ok(Vec::new());
+    }
+    if answer == "all" {
+        return Ok((0..allowances.len()).collect());
+    }
+
+    answer
+        .split(',')
+        .map(|part| {
+            part.trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index >= 1 && *index <= allowances.len())
+                .map(|index| index - 1)
+                .ok_or_else(|| format!("invalid selection: {}", part))
+        })
+        .collect()
+}
+
+async fn revoke(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    allowance: &Allowance,
+) -> Result<(), String> {
+    let data = Function::parse(APPROVE)?.encode_input(&[
+        Token::Address(abi::parse_address(&allowance.spender)?),
+        Token::Uint(U256::ZERO),
+    ])?;
+
+    let request = BroadcastTransactionRequest {
+        wallet_id: wallet_id.to_string(),
+        body: BroadcastTransactionBody {
+            kind: BroadcastTransactionBodyKind::Eip1559,
+            transaction: None,
+            data: Some(format!("0x{}", hex::encode(data))),
+            gas_limit: None,
+            nonce: None,
+            to: Some(allowance.token.clone()),
+            value: Some("0".to_string()),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_price: None,
+            psbt: None,
+            external_id: None,
+        },
+    };
+
+    let response = client
+        .wallets()
+        .broadcast_transaction(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    println!(
+        "  Revoked {} for {}: {} ({:?})",
+        allowance.token, allowance.spender, response.id, response.status
+    );
+    Ok(())
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let labels = load_labels(options.labels_file.as_deref())?;
+    let wallet = client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: options.wallet_id.clone(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let owner = wallet.address.ok_or("wallet has no address")?;
+    let network: ReadContractBodyNetwork = serde_json::to_value(&wallet.network)
+        .and_then(serde_json::from_value)
+        .map_err(|_| format!("{:?} does not support contract reads", wallet.network))?;
+
+    let mut tokens = BTreeSet::new();
+    let mut spenders = BTreeSet::new();
+    for spender in &options.spenders {
+        if !is_address(spender) {
+            return Err(format!("--spender {} is not an address", spender));
+        }
+        spenders.insert(spender.to_lowercase());
+    }
+
+    scan_history(client, &options.wallet_id, options.max_pages, &mut tokens).await?;
+    scan_approve_transactions(
+        client,
+        &options.wallet_id,
+        options.max_pages,
+        &mut tokens,
+        &mut spenders,
+    )
+    .await?;
+
+    println!(
+        "Checking {} tokens against {} candidate spenders for {}",
+        tokens.len(),
+        spenders.len(),
+        owner
+    );
+
+    let allowances = current_allowances(client, &network, &owner, &tokens, &spenders).await?;
+    if allowances.is_empty() {
+        println!("No outstanding allowances found");
+        return Ok(());
+    }
+
+    let token_list: Vec<String> = allowances.iter().map(|a| a.token.clone()).collect();
+    let mut token_client = TokenClient::new(client, network, MetadataCache::default());
+    token_client.prefetch_metadata(&token_list).await?;
+
+    println!("\nOutstanding allowances:");
+    for (index, allowance) in allowances.iter().enumerate() {
+        let metadata = token_client
+            .cache()
+            .get(token_client.network(), &allowance.token)
+            .cloned()
+            .unwrap_or_default();
+        let amount = match metadata.decimals {
+            _ if allowance.amount >= U256::MAX >> 1 => "unlimited".to_string(),
+            Some(decimals) => evm::format_units(allowance.amount, decimals),
+            None => format!("{} raw", allowance.amount),
+        };
+        println!(
+            "  {:>3}. {} {} -> {} ({})",
+            index + 1,
+            amount,
+            metadata.symbol.as_deref().unwrap_or(&allowance.token),
+            allowance.spender,
+            labels
+                .get(&allowance.spender)
+                .map(String::as_str)
+                .unwrap_or("unknown spender")
+        );
+    }
+
+    let selected = select(&allowances, options.revoke.as_deref())?;
+    if selected.is_empty() {
+        println!("Nothing revoked");
+        return Ok(());
+    }
+
+    println!("\nBroadcasting {} revoke transactions:", selected.len());
+    for index in selected {
+        if let Err(e) = revoke(client, &options.wallet_id, &allowances[index]).await {
+            eprintln!("  Failed to revoke #{}: {}", index + 1, e);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
@@ -0,0 +1,470 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/fee_history.rs"]
+mod fee_history;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        networks::types::GetFeesQueryNetwork,
+        wallets::types::{
+            GetWalletRequest, TransferAssetBody, TransferAssetBodyKind, TransferAssetRequest,
+        },
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use fee_history::FeeSample;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, env, fs, sync::Arc, time::Duration};
+
+const DEFAULT_QUEUE: &str = "scheduled_transfers.json";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_DEADLINE_MARGIN: Duration = Duration::from_secs(15 * 60);
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ScheduledTransfer {
+    id: String,
+    wallet_id: String,
+    network: String,
+    to: String,
+    amount: String,
+    contract: Option<String>,
+    tier: String,
+    max_fee: String,
+    deadline: u64,
+    created_at: u64,
+    state: String,
+    transfer_id: Option<String>,
+    submitted_fee: Option<String>,
+    error: Option<String>,
+}
+
+impl ScheduledTransfer {
+    fn is_queued(&self) -> bool {
+        self.state == "Queued"
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Queue {
+    next_id: u64,
+    transfers: BTreeMap<String, ScheduledTransfer>,
+}
+
+impl Queue {
+    fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("failed to parse {}: {}", path, e)),
+            Err(_) => Ok(Queue::default()),
+        }
+    }
+
+    fn save(&self, path: &str) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| format!("failed to write {}: {}", path, e))
+    }
+}
+
+struct Options {
+    command: String,
+    positional: Vec<String>,
+    queue: String,
+    contract: Option<String>,
+    tier: String,
+    max_fee: Option<String>,
+    deadline: Option<Duration>,
+    deadline_margin: Duration,
+    interval: Duration,
+    once: bool,
+}
+
+fn parse_secs(value: &str, flag: &str) -> Result<Duration, String> {
+    value
+        .parse()
+        .map(Duration::from_secs)
+        .map_err(|_| format!("invalid {}", flag))
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            command: args.next().unwrap_or_else(|| "list".to_string()),
+            positional: Vec::new(),
+            queue: DEFAULT_QUEUE.to_string(),
+            contract: None,
+            tier: "standard".to_string(),
+            max_fee: None,
+            deadline: None,
+            deadline_margin: DEFAULT_DEADLINE_MARGIN,
+            interval: DEFAULT_INTERVAL,
+            once: false,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--queue" => options.queue = value()?,
+                "--contract" => options.contract = Some(value()?),
+                "--priority" => {
+                    let tier = value()?.to_lowercase();
+                    if !fee_history::TIERS.contains(&tier.as_str()) {
+                        return Err(format!(
+                            "unknown priority {} (expected slow, standard or fast)",
+                            tier
+                        ));
+                    }
+                    options.tier = tier;
+                }
+                "--max-fee" => options.max_fee = Some(value()?),
+                "--deadline" => options.deadline = Some(parse_secs(&value()?, &arg)?),
+                "--deadline-margin" => options.deadline_margin = parse_secs(&value()?, &arg)?,
+                "--interval" => options.interval = parse_secs(&value()?, &arg)?,
+                "--once" => options.once = true,
+                _ => options.positional.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn numeric(value: &str) -> Option<f64> {
+    value.parse().ok()
+}
+
+async fn add(client: &DfnsApiClient, queue: &mut Queue, options: &Options) -> Result<(), String> {
+    let [wallet_id, to, amount] = options.positional.as_slice() else {
+        return Err(
+            "usage: add <wallet> <to> <amount> --max-fee <fee> --deadline <secs>".to_string(),
+        );
+    };
+    let max_fee = options.max_fee.clone().ok_or("--max-fee is required")?;
+    if numeric(&max_fee).is_none() {
+        return Err(format!("invalid --max-fee {}", max_fee));
+    }
+    let deadline = options.deadline.ok_or("--deadline is required")?;
+
+    let wallet = client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: wallet_id.clone(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let network: GetFeesQueryNetwork = evm::convert_network(&wallet.network)?;
+
+    queue.next_id += 1;
+    let now = fee_history::unix_now();
+    let transfer = ScheduledTransfer {
+        id: format!("scheduled-{}-{}", now, queue.next_id),
+        wallet_id: wallet_id.clone(),
+        network: fee_history::network_name(&network),
+        to: to.clone(),
+        amount: abi::parse_uint(amount)?.to_string(),
+        contract: options.contract.clone(),
+        tier: options.tier.clone(),
+        max_fee,
+        deadline: now + deadline.as_secs(),
+        created_at: now,
+        state: "Queued".to_string(),
+        transfer_id: None,
+        submitted_fee: None,
+        error: None,
+    };
+
+    println!(
+        "Queued {}: {} to {} on {} when {} fee <= {} (deadline in {}s)",
+        transfer.id,
+        transfer.amount,
+        transfer.to,
+        transfer.network,
+        transfer.tier,
+        transfer.max_fee,
+        deadline.as_secs()
+    );
+    queue.transfers.insert(transfer.id.clone(), transfer);
+    Ok(())
+}
+
+fn list(queue: &Queue) {
+    if queue.transfers.is_empty() {
+        println!("No scheduled transfers");
+        return;
+    }
+
+    let now = fee_history::unix_now();
+    for transfer in queue.transfers.values() {
+        let timing = if transfer.is_queued() {
+            format!("deadline in {}s", transfer.deadline.saturating_sub(now))
+        } else {
+            transfer
+                .transfer_id
+                .clone()
+                .or_else(|| transfer.error.clone())
+                .unwrap_or_default()
+        };
+        println!(
+            "{}  {:<9}  {} -> {}  {} on {}  max {} fee {}  {}",
+            transfer.id,
+            transfer.state,
+            transfer.wallet_id,
+            transfer.to,
+            transfer.amount,
+            transfer.network,
+            transfer.tier,
+            transfer.max_fee,
+            timing
+        );
+    }
+}
+
+async fn submit(
+    client: &DfnsApiClient,
+    transfer: &ScheduledTransfer,
+    tier: &str,
+) -> Result<String, String> {
+    let kind = match transfer.contract {
+        Some(_) => TransferAssetBodyKind::Erc20,
+        None => TransferAssetBodyKind::Native,
+    };
+    let request = TransferAssetRequest {
+        wallet_id: transfer.wallet_id.clone(),
+        body: TransferAssetBody {
+            kind,
+            to: transfer.to.clone(),
+            amount: Some(transfer.amount.clone()),
+            priority: Some(fee_history::priority(tier)),
+            memo: None,
+            external_id: Some(transfer.id.clone()),
+            create_destination_account: None,
+            asset_id: None,
+            metadata: None,
+            contract: transfer.contract.clone(),
+            token_id: None,
+            asset_code: None,
+            issuer: None,
+            mint: None,
+            master: None,
+        },
+    };
+
+    let response = client
+        .wallets()
+        .transfer_asset(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(response.id)
+}
+
+async fn process(
+    client: &DfnsApiClient,
+    queue: &mut Queue,
+    options: &Options,
+) -> Result<usize, String> {
+    let mut samples: BTreeMap<String, Result<FeeSample, String>> = BTreeMap::new();
+    for transfer in queue
+        .transfers
+        .values()
+        .filter(|transfer| transfer.is_queued())
+    {
+        if !samples.contains_key(&transfer.network) {
+            let sample = match fee_history::parse_network(&transfer.network) {
+                Ok(network) => fee_history::sample(client, network).await,
+                Err(e) => Err(e),
+            };
+            samples.insert(transfer.network.clone(), sample);
+        }
+    }
+
+    let queued: Vec<String> = queue
+        .transfers
+        .values()
+        .filter(|transfer| transfer.is_queued())
+        .map(|transfer| transfer.id.clone())
+        .collect();
+
+    let now = fee_history::unix_now();
+    let mut remaining = 0;
+    for id in queued {
+        let Some(transfer) = queue.transfers.get_mut(&id) else {
+            continue;
+        };
+        let current = match &samples[&transfer.network] {
+            Ok(sample) => sample
+                .tier(&transfer.tier)
+                .and_then(|tier| tier.fee())
+                .map(str::to_string),
+            Err(e) => {
+                eprintln!("{}: failed to read fees: {}", transfer.id, e);
+                None
+            }
+        };
+
+        let cheap_enough = match (
+            current.as_deref().and_then(numeric),
+            numeric(&transfer.max_fee),
+        ) {
+            (Some(current), Some(max_fee)) => current <= max_fee,
+            _ => false,
+        };
+        let deadline_near = now + options.deadline_margin.as_secs() >= transfer.deadline;
+
+        if !cheap_enough && !deadline_near {
+            println!(
+                "{}: waiting, {} fee {} above {} ({}s to deadline)",
+                transfer.id,
+                transfer.tier,
+                current.as_deref().unwrap_or("unknown"),
+                transfer.max_fee,
+                transfer.deadline.saturating_sub(now)
+            );
+            remaining += 1;
+            continue;
+        }
+
+        let tier = if cheap_enough {
+            transfer.tier.as_str()
+        } else {
+            "fast"
+        };
+        match submit(client, transfer, tier).await {
+            Ok(transfer_id) => {
+                println!(
+                    "{}: submitted as {} at {} fee {} ({})",
+                    transfer.id,
+                    transfer_id,
+                    tier,
+                    current.as_deref().unwrap_or("unknown"),
+                    if cheap_enough {
+                        "below threshold"
+                    } else {
+                        "deadline reached"
+                    }
+                );
+                transfer.state = "Submitted".to_string();
+                transfer.transfer_id = Some(transfer_id);
+                transfer.submitted_fee = current;
+            }
+            Err(e) if now >= transfer.deadline => {
+                eprintln!("{}: failed after deadline: {}", transfer.id, e);
+                transfer.state = "Failed".to_string();
+                transfer.error = Some(e);
+            }
+            Err(e) => {
+                eprintln!("{}: submission failed, will retry: {}", transfer.id, e);
+                transfer.error = Some(e);
+                remaining += 1;
+            }
+        }
+        queue.save(&options.queue)?;
+    }
+
+    Ok(remaining)
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let mut queue = Queue::load(&options.queue)?;
+
+    match options.command.as_str() {
+        "add" => add(client, &mut queue, options).await?,
+        "list" => list(&queue),
+        "remove" => {
+            let [id] = options.positional.as_slice() else {
+                return Err("usage: remove <scheduled-id>".to_string());
+            };
+            match queue.transfers.get(id) {
+                Some(transfer) if transfer.is_queued() => {
+                    queue.transfers.remove(id);
+                    println!("Removed {}", id);
+                }
+                Some(transfer) => {
+                    return Err(format!("{} is already {}", id, transfer.state));
+                }
+                None => return Err(format!("{} is not scheduled", id)),
+            }
+        }
+        "run" => loop {
+            let remaining = process(client, &mut queue, options).await?;
+            queue.save(&options.queue)?;
+            if options.once || remaining == 0 {
+                println!("{} transfers still queued", remaining);
+                break;
+            }
+            tokio::time::sleep(options.interval).await;
+        },
+        other => {
+            return Err(format!(
+                "unknown command {} (expected add, list, remove or run)",
+                other
+            ));
+        }
+    }
+
+    queue.save(&options.queue)
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
@@ -0,0 +1,479 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/evm.rs"]
+mod evm;
+#[path = "../common/message_signing.rs"]
+mod message_signing;
+
+use abi::{Function, Token, address_word, keccak256};
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::{
+        networks::types::ReadContractBodyNetwork,
+        wallets::types::{
+            GenerateSignatureBody, GenerateSignatureBodyKind, GenerateSignatureRequest,
+            GetWalletRequest, Message,
+        },
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use ruint::aliases::U256;
+use serde_json::{Value, json};
+use std::{env, sync::Arc};
+
+const PERMIT2_ADDRESS: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+const NAME: &str = "name()(string)";
+const VERSION: &str = "version()(string)";
+const NONCES: &str = "nonces(address)(uint256)";
+const DOMAIN_SEPARATOR: &str = "DOMAIN_SEPARATOR()(bytes32)";
+const PERMIT2_ALLOWANCE: &str = "allowance(address,address,address)(uint160,uint48,uint48)";
+const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const PERMIT_TYPE: &str =
+    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+const PERMIT_DETAILS_TYPE: &str =
+    "PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)";
+const PERMIT_SINGLE_TYPE: &str = concat!(
+    "PermitSingle(PermitDetails details,address spender,uint256 sigDeadline)",
+    "PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)"
+);
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    standard: String,
+    wallet_id: String,
+    token: String,
+    spender: String,
+    amount: U256,
+    deadline: U256,
+    expiration: U256,
+    chain_id: u64,
+    network: ReadContractBodyNetwork,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut args = env::args().skip(1);
+        let mut options = Options {
+            standard: args.next().unwrap_or_else(|| "eip2612".to_string()),
+            wallet_id: "example-wallet-id".to_string(),
+            token: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
+            spender: "0x1234567890abcdef1234567890abcdef12345678".to_string(),
+            amount: U256::from(1_000_000u64),
+            deadline: U256::from(u32::MAX),
+            expiration: U256::from(u32::MAX),
+            chain_id: 1,
+            network: ReadContractBodyNetwork::Ethereum,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--wallet" => options.wallet_id = value()?,
+                "--token" => options.token = value()?,
+                "--spender" => options.spender = value()?,
+                "--amount" => options.amount = parse_u256(&value()?)?,
+                "--deadline" => options.deadline = parse_u256(&value()?)?,
+                "--expiration" => options.expiration = parse_u256(&value()?)?,
+                "--chain-id" => {
+                    options.chain_id = value()?.parse().map_err(|_| "invalid chain id")?
+                }
+                "--network" => {
+                    let network = value()?;
+                    options.network =
+                        serde_json::from_value(serde_json::Value::String(network.clone()))
+                            .map_err(|_| format!("unsupported network: {}", network))?;
+                }
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_u256(value: &str) -> Result<U256, String> {
+    U256::from_str_radix(value, 10).map_err(|e| format!("invalid uint256 {}: {}", value, e))
+}
+
+fn uint_word(value: U256) -> [u8; 32] {
+    value.to_be_bytes::<32>()
+}
+
+fn hash_words(words: &[[u8; 32]]) -> [u8; 32] {
+    keccak256(&words.concat())
+}
+
+fn typed_data_digest(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut data = vec![0x19, 0x01];
+    data.extend_from_slice(domain_separator);
+    data.extend_from_slice(struct_hash);
+    keccak256(&data)
+}
+
+fn fits(name: &str, value: U256, bits: usize) -> Result<U256, String> {
+    if value.bit_len() > bits {
+        return Err(format!("{} {} does not fit in uint{}", name, value, bits));
+    }
+    Ok(value)
+}
+
+async fn read(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    contract: &str,
+    signature: &str,
+    args: &[Token],
+) -> Result<Vec<Token>, String> {
+    evm::call(
+        client,
+        network,
+        contract,
+        &Function::parse(signature)?,
+        args,
+    )
+    .await
+}
+
+fn output<T>(
+    tokens: Vec<Token>,
+    index: usize,
+    signature: &str,
+    convert: fn(Token) -> Option<T>,
+) -> Result<T, String> {
+    tokens
+        .into_iter()
+        .nth(index)
+        .and_then(convert)
+        .ok_or_else(|| format!("unexpected output from {}", signature))
+}
+
+async fn domain_separator(
+    client: &DfnsApiClient,
+    network: &ReadContractBodyNetwork,
+    contract: &str,
+) -> Result<[u8; 32], String> {
+    let tokens = read(client, network, contract, DOMAIN_SEPARATOR, &[]).await?;
+    output(tokens, 0, DOMAIN_SEPARATOR, Token::into_bytes)?
+        .try_into()
+        .map_err(|_| "DOMAIN_SEPARATOR is not 32 bytes".to_string())
+}
+
+struct PreparedPermit {
+    digest: [u8; 32],
+    typed_data: Value,
+}
+
+impl PreparedPermit {
+    fn signature_body(&self) -> Result<GenerateSignatureBody, String> {
+        let field = |key: &str| self.typed_data[key].clone();
+        let mut types = field("types");
+        if let Some(types) = types.as_object_mut() {
+            types.remove("EIP712Domain");
+        }
+
+        Ok(GenerateSignatureBody {
+            kind: GenerateSignatureBodyKind::Eip712,
+            message: Some(
+                serde_json::from_value::<Message>(field("message"))
+                    .map_err(|e| format!("invalid typed data message: {}", e))?,
+            ),
+            external_id: None,
+            sign_doc: None,
+            hash: None,
+            taproot_merkle_root: None,
+            transaction: None,
+            domain: Some(
+                serde_json::from_value(field("domain"))
+                    .map_err(|e| format!("invalid typed data domain: {}", e))?,
+            ),
+            types: Some(
+                serde_json::from_value(types)
+                    .map_err(|e| format!("invalid typed data types: {}", e))?,
+            ),
+            psbt: None,
+            format: None,
+        })
+    }
+}
+
+async fn prepare_eip2612(
+    client: &DfnsApiClient,
+    options: &Options,
+    owner: &str,
+) -> Result<PreparedPermit, String> {
+    let network = &options.network;
+    let token = options.token.as_str();
+    let name = output(
+        read(client, network, token, NAME, &[]).await?,
+        0,
+        NAME,
+        Token::into_string,
+    )?;
+    let owner_token = Token::Address(abi::parse_address(owner)?);
+    let nonce = output(
+        read(client, network, token, NONCES, &[owner_token]).await?,
+        0,
+        NONCES,
+        Token::into_uint,
+    )?;
+    let domain_separator = domain_separator(client, network, token).await?;
+
+    let version = read(client, network, token, VERSION, &[])
+        .await
+        .and_then(|tokens| output(tokens, 0, VERSION, Token::into_string))
+        .unwrap_or_else(|_| "1".to_string());
+    let expected_separator = hash_words(&[
+        keccak256(EIP712_DOMAIN_TYPE.as_bytes()),
+        keccak256(name.as_bytes()),
+        keccak256(version.as_bytes()),
+        uint_word(U256::from(options.chain_id)),
+        address_word(&options.token)?,
+    ]);
+    if expected_separator != domain_separator {
+        return Err(format!(
+            "DOMAIN_SEPARATOR does not match {} v{} on chain {}, cannot build typed data",
+            name, version, options.chain_id
+        ));
+    }
+
+    let struct_hash = hash_words(&[
+        keccak256(PERMIT_TYPE.as_bytes()),
+        address_word(owner)?,
+        address_word(&options.spender)?,
+        uint_word(options.amount),
+        uint_word(nonce),
+        uint_word(options.deadline),
+    ]);
+
+    Ok(PreparedPermit {
+        digest: typed_data_digest(&domain_separator, &struct_hash),
+        typed_data: json!({
+            "domain": {
+                "name": name,
+                "version": version,
+                "chainId": options.chain_id,
+                "verifyingContract": options.token,
+            },
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "Permit": [
+                    { "name": "owner", "type": "address" },
+                    { "name": "spender", "type": "address" },
+                    { "name": "value", "type": "uint256" },
+                    { "name": "nonce", "type": "uint256" },
+                    { "name": "deadline", "type": "uint256" },
+                ],
+            },
+            "primaryType": "Permit",
+            "message": {
+                "owner": owner,
+                "spender": options.spender,
+                "value": options.amount.to_string(),
+                "nonce": nonce.to_string(),
+                "deadline": options.deadline.to_string(),
+            },
+        }),
+    })
+}
+
+async fn prepare_permit2(
+    client: &DfnsApiClient,
+    options: &Options,
+    owner: &str,
+) -> Result<PreparedPermit, String> {
+    let network = &options.network;
+    let args = [
+        Token::Address(abi::parse_address(owner)?),
+        Token::Address(abi::parse_address(&options.token)?),
+        Token::Address(abi::parse_address(&options.spender)?),
+    ];
+    let nonce = output(
+        read(client, network, PERMIT2_ADDRESS, PERMIT2_ALLOWANCE, &args).await?,
+        2,
+        PERMIT2_ALLOWANCE,
+        Token::into_uint,
+    )?;
+    let amount = fits("amount", options.amount, 160)?;
+    let expiration = fits("expiration", options.expiration, 48)?;
+    let nonce = fits("nonce", nonce, 48)?;
+    let domain_separator = domain_separator(client, network, PERMIT2_ADDRESS).await?;
+
+    let details_hash = hash_words(&[
+        keccak256(PERMIT_DETAILS_TYPE.as_bytes()),
+        address_word(&options.token)?,
+        uint_word(amount),
+        uint_word(expiration),
+        uint_word(nonce),
+    ]);
+    let struct_hash = hash_words(&[
+        keccak256(PERMIT_SINGLE_TYPE.as_bytes()),
+        details_hash,
+        address_word(&options.spender)?,
+        uint_word(options.deadline),
+    ]);
+
+    Ok(PreparedPermit {
+        digest: typed_data_digest(&domain_separator, &struct_hash),
+        typed_data: json!({
+            "domain": {
+                "name": "Permit2",
+                "chainId": options.chain_id,
+                "verifyingContract": PERMIT2_ADDRESS,
+            },
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "PermitSingle": [
+                    { "name": "details", "type": "PermitDetails" },
+                    { "name": "spender", "type": "address" },
+                    { "name": "sigDeadline", "type": "uint256" },
+                ],
+                "PermitDetails": [
+                    { "name": "token", "type": "address" },
+                    { "name": "amount", "type": "uint160" },
+                    { "name": "expiration", "type": "uint48" },
+                    { "name": "nonce", "type": "uint48" },
+                ],
+            },
+            "primaryType": "PermitSingle",
+            "message": {
+                "details": {
+                    "token": options.token,
+                    "amount": amount.to_string(),
+                    "expiration": expiration.to_string(),
+                    "nonce": nonce.to_string(),
+                },
+                "spender": options.spender,
+                "sigDeadline": options.deadline.to_string(),
+            },
+        }),
+    })
+}
+
+async fn run(client: &DfnsApiClient, options: &Options) -> Result<(), String> {
+    let wallet = client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: options.wallet_id.clone(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let owner = wallet.address.ok_or("wallet has no address")?;
+
+    let permit = match options.standard.as_str() {
+        "eip2612" => prepare_eip2612(client, options, &owner).await?,
+        "permit2" => prepare_permit2(client, options, &owner).await?,
+        other => {
+            return Err(format!(
+                "unknown standard {} (expected eip2612 or permit2)",
+                other
+            ));
+        }
+    };
+
+    println!(
+        "Typed data:\n{}",
+        serde_json::to_string_pretty(&permit.typed_data).map_err(|e| e.to_string())?
+    );
+
+    let request = GenerateSignatureRequest {
+        wallet_id: options.wallet_id.clone(),
+        body: permit.signature_body()?,
+    };
+
+    let response = client
+        .wallets()
+        .generate_signature(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let signature = response
+        .signature
+        .ok_or_else(|| format!("signature not available yet: {:?}", response.status))?;
+
+    let recid = signature
+        .recid
+        .ok_or("signature is missing a recovery id")? as u8;
+    let parsed = message_signing::parse_signature(&signature.r, &signature.s)?;
+    let signer = message_signing::recover(&permit.digest, &parsed, recid)?;
+    if !message_signing::eth_address(&signer).eq_ignore_ascii_case(&owner) {
+        return Err("signature does not recover to the wallet address".to_string());
+    }
+
+    let (r, s) = parsed.split_bytes();
+    println!("\nPermit signature:");
+    println!("  v: {}", 27 + recid);
+    println!("  r: {}", message_signing::hex_prefixed(&r));
+    println!("  s: {}", message_signing::hex_prefixed(&s));
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &options).await {
+        eprintln!("Error: {}", e);
+    }
+}
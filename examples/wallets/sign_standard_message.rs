@@ -0,0 +1,281 @@
+#[path = "../common/abi.rs"]
+mod abi;
+#[path = "../common/message_signing.rs"]
+mod message_signing;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::{
+        GenerateSignatureBody, GenerateSignatureBodyKind, GenerateSignatureRequest,
+        GetWalletRequest, Message,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use message_signing::{Bip137AddressKind, Bip322P2wpkh, SiweMessage};
+use std::{
+    env,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct RawSignature {
+    r: String,
+    s: String,
+    recid: u8,
+}
+
+enum Payload {
+    Hash([u8; 32]),
+    Message(Vec<u8>),
+}
+
+async fn sign(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    payload: &Payload,
+) -> Result<RawSignature, String> {
+    let (kind, message, hash) = match payload {
+        Payload::Hash(hash) => (
+            GenerateSignatureBodyKind::Hash,
+            None,
+            Some(message_signing::hex_prefixed(hash)),
+        ),
+        Payload::Message(message) => (
+            GenerateSignatureBodyKind::Message,
+            Some(Message::String(message_signing::hex_prefixed(message))),
+            None,
+        ),
+    };
+
+    let request = GenerateSignatureRequest {
+        wallet_id: wallet_id.to_string(),
+        body: GenerateSignatureBody {
+            kind,
+            message,
+            external_id: None,
+            sign_doc: None,
+            hash,
+            taproot_merkle_root: None,
+            transaction: None,
+            domain: None,
+            types: None,
+            psbt: None,
+            format: None,
+        },
+    };
+
+    let response = client
+        .wallets()
+        .generate_signature(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let signature = response
+        .signature
+        .ok_or_else(|| format!("signature not available yet: {:?}", response.status))?;
+
+    Ok(RawSignature {
+        r: signature.r,
+        s: signature.s,
+        recid: signature.recid.unwrap_or_default() as u8,
+    })
+}
+
+fn siwe_message(address: &str, args: &[String]) -> Result<String, String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?;
+    let mut message = SiweMessage {
+        domain: "example.com".to_string(),
+        address: abi::checksum_address(&abi::parse_address(address)?),
+        statement: None,
+        uri: "https://example.com/login".to_string(),
+        chain_id: 1,
+        nonce: format!("{:016x}", now.as_nanos()),
+        issued_at: message_signing::utc_timestamp(now.as_secs()),
+        expiration_time: None,
+        not_before: None,
+        request_id: None,
+        resources: Vec::new(),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{} expects a value", arg))
+        };
+        match arg.as_str() {
+            "--domain" => message.domain = value()?,
+            "--statement" => message.statement = Some(value()?),
+            "--uri" => message.uri = value()?,
+            "--chain-id" => message.chain_id = value()?.parse().map_err(|_| "invalid chain id")?,
+            "--nonce" => message.nonce = value()?,
+            "--issued-at" => message.issued_at = value()?,
+            "--expiration-time" => message.expiration_time = Some(value()?),
+            "--not-before" => message.not_before = Some(value()?),
+            "--request-id" => message.request_id = Some(value()?),
+            "--resource" => message.resources.push(value()?),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    message.to_message()
+}
+
+async fn run(
+    client: &DfnsApiClient,
+    wallet_id: &str,
+    standard: &str,
+    args: &[String],
+) -> Result<(), String> {
+    let wallet = client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: wallet_id.to_string(),
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let address = wallet.address.unwrap_or_default();
+    let public_key = hex::decode(wallet.signing_key.public_key.trim_start_matches("0x"))
+        .map_err(|e| e.to_string())?;
+    let text = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "Hello, World!".to_string());
+
+    match standard {
+        "eip191" | "siwe" => {
+            let message = if standard == "siwe" {
+                siwe_message(&address, args)?
+            } else {
+                text
+            };
+            println!("Message:\n{}\n", message);
+
+            let hash = message_signing::eip191_hash(message.as_bytes());
+            let raw = sign(client, wallet_id, &Payload::Hash(hash)).await?;
+            let signature = message_signing::parse_signature(&raw.r, &raw.s)?;
+            let valid = message_signing::verify_eip191(
+                message.as_bytes(),
+                &signature,
+                raw.recid,
+                &address,
+            )?;
+
+            println!(
+                "Signature: {}",
+                message_signing::eth_signature(&signature, raw.recid)
+            );
+            println!("Recovers to {}: {}", address, valid);
+        }
+        "bip137" => {
+            let kind = match args.get(1).map(String::as_str) {
+                Some("p2pkh") => Bip137AddressKind::P2pkhCompressed,
+                Some("p2sh-p2wpkh") => Bip137AddressKind::P2shP2wpkh,
+                _ => Bip137AddressKind::P2wpkh,
+            };
+
+            let hash = message_signing::bip137_hash(text.as_bytes());
+            let raw = sign(client, wallet_id, &Payload::Hash(hash)).await?;
+            let signature = message_signing::parse_signature(&raw.r, &raw.s)?;
+            let encoded = message_signing::bip137_signature(&signature, raw.recid, &kind);
+            let valid = message_signing::verify_bip137(text.as_bytes(), &encoded, &public_key)?;
+
+            println!("Signature: {}", encoded);
+            println!("Valid: {}", valid);
+        }
+        "bip322" => {
+            let proof = Bip322P2wpkh::new(text.as_bytes(), &public_key);
+            let raw = sign(client, wallet_id, &Payload::Hash(proof.sighash())).await?;
+            let signature = message_signing::parse_signature(&raw.r, &raw.s)?;
+
+            println!("Signature: {}", proof.encode_signature(&signature));
+            println!("Valid: {}", proof.verify(&signature)?);
+        }
+        "solana" => {
+            let signer: [u8; 32] = public_key
+                .as_slice()
+                .try_into()
+                .map_err(|_| "wallet public key is not an ed25519 key")?;
+            let message =
+                message_signing::solana_offchain_message(text.as_bytes(), [0u8; 32], &[signer])?;
+            let raw = sign(client, wallet_id, &Payload::Message(message.clone())).await?;
+
+            let mut signature =
+                hex::decode(raw.r.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+            signature
+                .extend(hex::decode(raw.s.trim_start_matches("0x")).map_err(|e| e.to_string())?);
+            let valid = message_signing::verify_ed25519(&message, &signature, &public_key)?;
+
+            println!("Signature: {}", bs58::encode(&signature).into_string());
+            println!("Valid: {}", valid);
+        }
+        other => {
+            return Err(format!(
+                "unknown standard {} (expected eip191, siwe, bip137, bip322 or solana)",
+                other
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args = env::args().skip(1);
+    let standard = args.next().unwrap_or_else(|| "eip191".to_string());
+    let wallet_id = args
+        .next()
+        .unwrap_or_else(|| "example-wallet-id".to_string());
+    let rest: Vec<String> = args.collect();
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    if let Err(e) = run(&client, &wallet_id, &standard, &rest).await {
+        eprintln!("Error: {}", e);
+    }
+}
@@ -0,0 +1,253 @@
+#[path = "../common/solana.rs"]
+mod solana;
+
+use async_trait::async_trait;
+use dfns_sdk_rs::{
+    DfnsApiClient,
+    api::wallets::types::{
+        BroadcastTransactionBody, BroadcastTransactionBodyKind, BroadcastTransactionRequest,
+        GenerateSignatureBody, GenerateSignatureBodyKind, GenerateSignatureRequest,
+        GetWalletRequest,
+    },
+    error::DfnsError,
+    models::generic::DfnsBaseApiOptions,
+    signer::{
+        CredentialSigner, FirstFactorAssertion, FirstFactorAssertionKind, UserActionChallenge,
+    },
+};
+use solana::{Instruction, Message, Pubkey, SignedTransaction};
+use std::{env, sync::Arc};
+
+struct ExampleSigner {
+    cred_id: String,
+    signature: String,
+}
+
+impl ExampleSigner {
+    fn new(cred_id: String, signature: String) -> Self {
+        Self { cred_id, signature }
+    }
+}
+
+#[async_trait]
+impl CredentialSigner for ExampleSigner {
+    async fn sign(
+        &self,
+        _challenge: UserActionChallenge,
+    ) -> Result<FirstFactorAssertion, DfnsError> {
+        Ok(FirstFactorAssertion {
+            credential_assertion: None,
+            kind: FirstFactorAssertionKind::Key,
+            password: Some(self.signature.clone()),
+        })
+    }
+}
+
+struct Options {
+    wallet_id: String,
+    recipient: String,
+    amount: u64,
+    recent_blockhash: String,
+    mint: Option<String>,
+    decimals: u8,
+    token_2022: bool,
+    v0: bool,
+    sign_only: bool,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, String> {
+        let mut options = Options {
+            wallet_id: "example-wallet-id".to_string(),
+            recipient: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+            amount: 1_000_000,
+            recent_blockhash: "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N".to_string(),
+            mint: None,
+            decimals: 6,
+            token_2022: false,
+            v0: false,
+            sign_only: false,
+        };
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", arg))
+            };
+            match arg.as_str() {
+                "--wallet" => options.wallet_id = value()?,
+                "--to" => options.recipient = value()?,
+                "--amount" => {
+                    options.amount = value()?.parse().map_err(|_| "invalid --amount")?;
+                }
+                "--blockhash" => options.recent_blockhash = value()?,
+                "--mint" => options.mint = Some(value()?),
+                "--decimals" => {
+                    options.decimals = value()?.parse().map_err(|_| "invalid --decimals")?;
+                }
+                "--token-2022" => options.token_2022 = true,
+                "--v0" => options.v0 = true,
+                "--sign-only" => options.sign_only = true,
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn build_instructions(owner: &Pubkey, options: &Options) -> Result<Vec<Instruction>, String> {
+    let recipient = Pubkey::from_base58(&options.recipient)?;
+
+    match &options.mint {
+        None => Ok(vec![solana::system_transfer(
+            owner,
+            &recipient,
+            options.amount,
+        )]),
+        Some(mint) => {
+            let token_program = Pubkey::from_base58(if options.token_2022 {
+                solana::TOKEN_2022_PROGRAM_ID
+            } else {
+                solana::TOKEN_PROGRAM_ID
+            })?;
+            solana::spl_transfer_with_ata(
+                owner,
+                owner,
+                &recipient,
+                &Pubkey::from_base58(mint)?,
+                options.amount,
+                options.decimals,
+                &token_program,
+            )
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let signer = Arc::new(ExampleSigner::new(
+        "example-cred-id".to_string(),
+        "example-signature".to_string(),
+    ));
+
+    let base_options = DfnsBaseApiOptions {
+        app_id: "your-app-id".to_string(),
+        auth_token: Some("your-auth-token".to_string()),
+        base_url: Some("https://api.dfns.ninja".to_string()),
+        app_secret: None,
+    };
+
+    let client = DfnsApiClient::new(base_options, Some(signer));
+
+    let wallet = match client
+        .wallets()
+        .get_wallet(GetWalletRequest {
+            wallet_id: options.wallet_id.clone(),
+        })
+        .await
+    {
+        Ok(wallet) => wallet,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            return;
+        }
+    };
+
+    let message = wallet
+        .address
+        .ok_or_else(|| "wallet has no address".to_string())
+        .and_then(|address| Pubkey::from_base58(&address))
+        .and_then(|owner| {
+            let instructions = build_instructions(&owner, &options)?;
+            if options.v0 {
+                Message::v0(&owner, &instructions, &options.recent_blockhash, &[])
+            } else {
+                Message::legacy(&owner, &instructions, &options.recent_blockhash)
+            }
+        });
+
+    let transaction = match message.and_then(|message| message.to_unsigned_transaction_hex()) {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            eprintln!("Error building transaction: {}", e);
+            return;
+        }
+    };
+
+    println!("Unsigned transaction: {}", transaction);
+
+    if options.sign_only {
+        let request = GenerateSignatureRequest {
+            wallet_id: options.wallet_id,
+            body: GenerateSignatureBody {
+                kind: GenerateSignatureBodyKind::Transaction,
+                message: None,
+                external_id: None,
+                sign_doc: None,
+                hash: None,
+                taproot_merkle_root: None,
+                transaction: Some(transaction),
+                domain: None,
+                types: None,
+                psbt: None,
+                format: None,
+            },
+        };
+
+        match client.wallets().generate_signature(request).await {
+            Ok(response) => match response
+                .signed_data
+                .as_deref()
+                .map(SignedTransaction::decode)
+            {
+                Some(Ok(signed)) => {
+                    println!("Fully signed: {}", signed.is_fully_signed());
+                    if let Some(id) = signed.transaction_id() {
+                        println!("Transaction ID: {}", id);
+                    }
+                    match signed.to_base64() {
+                        Ok(encoded) => println!("Signed transaction (base64): {}", encoded),
+                        Err(e) => eprintln!("Error encoding signed transaction: {}", e),
+                    }
+                }
+                Some(Err(e)) => eprintln!("Error decoding signed transaction: {}", e),
+                None => println!("Signature pending: {:?}", response.status),
+            },
+            Err(e) => eprintln!("Error: {:?}", e),
+        }
+        return;
+    }
+
+    let request = BroadcastTransactionRequest {
+        wallet_id: options.wallet_id,
+        body: BroadcastTransactionBody {
+            kind: BroadcastTransactionBodyKind::Transaction,
+            transaction: Some(transaction),
+            data: None,
+            gas_limit: None,
+            nonce: None,
+            to: None,
+            value: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_price: None,
+            psbt: None,
+            external_id: None,
+        },
+    };
+
+    match client.wallets().broadcast_transaction(request).await {
+        Ok(result) => println!("Transaction broadcasted: {:?}", result),
+        Err(e) => eprintln!("Error: {:?}", e),
+    }
+}